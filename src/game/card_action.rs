@@ -1,39 +1,71 @@
-use crate::state::Card;
+use crate::state::{Card, PlayerId};
 use crate::Error;
 use fehler::{throw, throws};
 
+#[derive(Clone, Debug)]
 pub struct CardAction {
-    card: Card,
-    current: usize,
-    target: Option<usize>,
-    guess: Option<Card>,
+    card: Box<dyn Card>,
+    current: PlayerId,
+    target: Option<PlayerId>,
+    second_target: Option<PlayerId>,
+    guess: Option<Box<dyn Card>>,
 }
 
 impl CardAction {
-    pub fn new(
-        card: Card,
-        current: usize,
-        target: Option<usize>,
-        guess: Option<Card>,
+    pub fn new<C: Card + 'static>(
+        card: C,
+        current: PlayerId,
+        target: Option<PlayerId>,
+        guess: Option<C>,
+    ) -> CardAction {
+        CardAction {
+            card: Box::new(card),
+            current,
+            target,
+            second_target: None,
+            guess: guess.map(|g| Box::new(g) as Box<dyn Card>),
+        }
+    }
+
+    /// Creates an action from cards that have already been boxed, such as
+    /// cards loaded from a file.
+    pub(crate) fn from_parts(
+        card: Box<dyn Card>,
+        current: PlayerId,
+        target: Option<PlayerId>,
+        second_target: Option<PlayerId>,
+        guess: Option<Box<dyn Card>>,
     ) -> CardAction {
         CardAction {
             card,
             current,
             target,
+            second_target,
             guess,
         }
     }
 
-    pub fn card(&self) -> Card {
-        self.card
+    /// Returns this action with a second target, for cards that affect
+    /// two players.
+    pub fn with_second_target(mut self, second_target: PlayerId) -> CardAction {
+        self.second_target = Some(second_target);
+        self
     }
 
-    pub fn current(&self) -> usize {
+    pub fn card(&self) -> &dyn Card {
+        self.card.as_ref()
+    }
+
+    pub fn current(&self) -> PlayerId {
         self.current
     }
 
+    pub fn has_target(&self) -> bool {
+        self.target.is_some()
+    }
+
     #[throws]
-    pub fn target(&self) -> usize {
+    pub fn target(&self) -> PlayerId {
         if let Some(target) = self.target {
             target
         } else {
@@ -41,10 +73,41 @@ impl CardAction {
         }
     }
 
+    pub fn has_second_target(&self) -> bool {
+        self.second_target.is_some()
+    }
+
+    #[throws]
+    pub fn second_target(&self) -> PlayerId {
+        if let Some(second_target) = self.second_target {
+            second_target
+        } else {
+            throw!(Error::BadActionMissingSecondTarget);
+        }
+    }
+
+    pub fn has_guess(&self) -> bool {
+        self.guess.is_some()
+    }
+
     #[throws]
-    pub fn guess(&self) -> Card {
-        if let Some(guess) = self.guess {
-            guess
+    pub fn guess(&self) -> &dyn Card {
+        if let Some(guess) = &self.guess {
+            guess.as_ref()
+        } else {
+            throw!(Error::BadActionMissingGuess);
+        }
+    }
+
+    /// A copy of the card, for events that outlive the action.
+    pub(crate) fn boxed_card(&self) -> Box<dyn Card> {
+        self.card.clone()
+    }
+
+    #[throws]
+    pub(crate) fn boxed_guess(&self) -> Box<dyn Card> {
+        if let Some(guess) = &self.guess {
+            guess.clone()
         } else {
             throw!(Error::BadActionMissingGuess);
         }
@@ -54,20 +117,33 @@ impl CardAction {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::state::OriginalCard;
 
     #[test]
     #[throws]
     fn test_basic() {
-	let action = CardAction::new(Card::Baron, 1, None, None);
-	assert_eq!(Card::Baron, action.card());
-	assert_eq!(1, action.current());
-	assert!(action.target().is_err());
-	assert!(action.guess().is_err());
-
-	let full_action = CardAction::new(Card::Prince, 3, Some(2), Some(Card::Princess));
-	assert_eq!(Card::Prince, full_action.card());
-	assert_eq!(3, full_action.current());
-	assert_eq!(2, full_action.target()?);
-	assert_eq!(Card::Princess, full_action.guess()?);
+        let action = CardAction::new(OriginalCard::Baron, PlayerId(1), None, None);
+        assert!(OriginalCard::Baron.is_same_card(action.card()));
+        assert_eq!(PlayerId(1), action.current());
+        assert!(action.target().is_err());
+        assert!(action.second_target().is_err());
+        assert!(action.guess().is_err());
+
+        let full_action = CardAction::new(
+            OriginalCard::Prince,
+            PlayerId(3),
+            Some(PlayerId(2)),
+            Some(OriginalCard::Princess),
+        );
+        assert!(OriginalCard::Prince.is_same_card(full_action.card()));
+        assert_eq!(PlayerId(3), full_action.current());
+        assert_eq!(PlayerId(2), full_action.target()?);
+        assert!(OriginalCard::Princess.is_same_card(full_action.guess()?));
+
+        let two_targets =
+            CardAction::new(OriginalCard::Baron, PlayerId(0), Some(PlayerId(1)), None)
+                .with_second_target(PlayerId(2));
+        assert_eq!(PlayerId(1), two_targets.target()?);
+        assert_eq!(PlayerId(2), two_targets.second_target()?);
     }
 }
@@ -1,13 +1,36 @@
 use crate::game::card_action::CardAction;
+use crate::game::validation::{ActionPart, Violation};
+use crate::state::{Card, PlayerId};
 use crate::Error;
 use fehler::{throw, throws};
 use std::collections::HashSet;
 
+/// A constraint on playing a card that depends on the other cards in the
+/// player's hand.
+#[derive(Debug)]
+pub enum HandConstraint {
+    Unconstrained,
+    /// The card may not be played while the player also holds the named
+    /// card. The player must play the named card instead.
+    MustPlayInstead(&'static str),
+}
+
+/// Whether a card may, or must, target a second player.
+#[derive(Debug, Eq, PartialEq)]
+pub enum SecondTarget {
+    NotAllowed,
+    Optional,
+    Required,
+}
+
 #[derive(Debug)]
 pub struct CardRules {
     target_required: bool,
     current_allowed_as_target: bool,
     guess_required: bool,
+    hand_constraint: HandConstraint,
+    second_target: SecondTarget,
+    forbidden_guess: Option<&'static str>,
 }
 
 impl CardRules {
@@ -20,55 +43,289 @@ impl CardRules {
             target_required,
             current_allowed_as_target,
             guess_required,
+            hand_constraint: HandConstraint::Unconstrained,
+            second_target: SecondTarget::NotAllowed,
+            forbidden_guess: None,
+        }
+    }
+
+    /// Returns these rules, allowing or requiring a second target.
+    pub const fn with_second_target(self, second_target: SecondTarget) -> CardRules {
+        CardRules {
+            second_target,
+            ..self
+        }
+    }
+
+    /// Returns these rules with the additional constraint that the guess
+    /// may not be the named card.
+    pub const fn forbid_guess(self, card_name: &'static str) -> CardRules {
+        CardRules {
+            forbidden_guess: Some(card_name),
+            ..self
+        }
+    }
+
+    /// Returns these rules with the additional constraint that the card may
+    /// not be played while the player is holding the named card.
+    pub const fn must_play_instead(self, card_name: &'static str) -> CardRules {
+        CardRules {
+            hand_constraint: HandConstraint::MustPlayInstead(card_name),
+            ..self
         }
     }
 
+    pub fn target_required(&self) -> bool {
+        self.target_required
+    }
+
+    pub fn current_allowed_as_target(&self) -> bool {
+        self.current_allowed_as_target
+    }
+
+    pub fn guess_required(&self) -> bool {
+        self.guess_required
+    }
+
+    pub fn second_target(&self) -> &SecondTarget {
+        &self.second_target
+    }
+
+    /// Checks that the action is allowed. `hand` is the current player's
+    /// hand, including the card being played.
     #[throws]
     pub fn action_allowed(
         &self,
         action: &CardAction,
-        current_player: usize,
-        active: &HashSet<usize>,
-        protected: &HashSet<usize>,
+        current_player: PlayerId,
+        active: &HashSet<PlayerId>,
+        protected: &HashSet<PlayerId>,
+        hand: &[Box<dyn Card>],
     ) {
-        CardRules::player_is_current(action, current_player)?;
-        self.target_is_valid(action, current_player, active, protected)?;
-        self.guess_is_valid(action)?;
+        let violations = self.action_violations(action, current_player, active, protected, hand);
+        if let Some(violation) = violations.into_iter().next() {
+            throw!(violation.into_error());
+        }
     }
 
-    #[throws]
-    fn guess_is_valid(&self, action: &CardAction) {
-        if self.guess_required {
-            action.guess()?;
+    /// Like `action_allowed`, but returns every constraint that the action
+    /// violates instead of stopping at the first. The first violation is
+    /// the error that `action_allowed` returns.
+    pub fn action_violations(
+        &self,
+        action: &CardAction,
+        current_player: PlayerId,
+        active: &HashSet<PlayerId>,
+        protected: &HashSet<PlayerId>,
+        hand: &[Box<dyn Card>],
+    ) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        CardRules::check_player(action, current_player, &mut violations);
+        self.check_hand(action, hand, &mut violations);
+        let no_target_allowed = self.no_target_allowed(action, current_player, active, protected);
+        self.check_targets(
+            action,
+            current_player,
+            active,
+            protected,
+            no_target_allowed,
+            &mut violations,
+        );
+        self.check_guess(action, no_target_allowed, &mut violations);
+        violations
+    }
+
+    fn check_hand(
+        &self,
+        action: &CardAction,
+        hand: &[Box<dyn Card>],
+        violations: &mut Vec<Violation>,
+    ) {
+        if let HandConstraint::MustPlayInstead(name) = self.hand_constraint {
+            let played = hand.iter().position(|c| c.is_same_card(action.card()));
+            let holding = hand
+                .iter()
+                .enumerate()
+                .any(|(index, c)| Some(index) != played && c.name() == name);
+            if holding {
+                violations.push(Violation::new(
+                    ActionPart::Card,
+                    Error::BadActionMustPlayCard(name.to_string()),
+                ));
+            }
         }
     }
 
-    #[throws]
-    fn target_is_valid(
+    fn check_guess(
         &self,
         action: &CardAction,
-        current_player: usize,
-        active: &HashSet<usize>,
-        protected: &HashSet<usize>,
+        no_target_allowed: bool,
+        violations: &mut Vec<Violation>,
     ) {
-        if self.target_required {
-            let target = action.target()?;
-            if !active.contains(&target) {
-                throw!(Error::BadActionTargetingInactive(target));
+        // A guess is meaningless when the card has no target. When the
+        // target is missing but needed, the target is the mistake, not the
+        // guess.
+        if self.guess_required && action.has_target() {
+            match action.guess() {
+                Ok(guess) if Some(guess.name()) == self.forbidden_guess => {
+                    violations.push(Violation::new(
+                        ActionPart::Guess,
+                        Error::BadActionInvalidGuess(guess.name().to_string()),
+                    ))
+                }
+                Ok(_) => {}
+                Err(error) => violations.push(Violation::new(ActionPart::Guess, error)),
             }
-            if protected.contains(&target) {
-                throw!(Error::BadActionTargetingProtected(target));
+        } else if action.has_guess() && (!self.guess_required || no_target_allowed) {
+            violations.push(Violation::new(
+                ActionPart::Guess,
+                Error::BadActionUnexpectedGuess,
+            ));
+        }
+    }
+
+    /// Returns the number of players that the current player could target.
+    fn valid_target_count(
+        &self,
+        current_player: PlayerId,
+        active: &HashSet<PlayerId>,
+        protected: &HashSet<PlayerId>,
+    ) -> usize {
+        active
+            .iter()
+            .filter(|player| {
+                !protected.contains(player)
+                    && (self.current_allowed_as_target || **player != current_player)
+            })
+            .count()
+    }
+
+    fn check_target(
+        &self,
+        part: ActionPart,
+        target: PlayerId,
+        current_player: PlayerId,
+        active: &HashSet<PlayerId>,
+        protected: &HashSet<PlayerId>,
+        violations: &mut Vec<Violation>,
+    ) {
+        if !active.contains(&target) {
+            violations.push(Violation::new(
+                part,
+                Error::BadActionTargetingInactive(target),
+            ));
+        }
+        if protected.contains(&target) {
+            violations.push(Violation::new(
+                part,
+                Error::BadActionTargetingProtected(target),
+            ));
+        }
+        if !self.current_allowed_as_target && current_player == target {
+            violations.push(Violation::new(part, Error::BadActionCannotTargetSelf));
+        }
+    }
+
+    /// Returns true if the action has no target because there is nobody
+    /// that it could target.
+    fn no_target_allowed(
+        &self,
+        action: &CardAction,
+        current_player: PlayerId,
+        active: &HashSet<PlayerId>,
+        protected: &HashSet<PlayerId>,
+    ) -> bool {
+        // If every other player is protected, the card may be played with no target.
+        let targets_needed = if self.second_target == SecondTarget::Required {
+            2
+        } else {
+            1
+        };
+        !action.has_target()
+            && self.valid_target_count(current_player, active, protected) < targets_needed
+    }
+
+    fn check_targets(
+        &self,
+        action: &CardAction,
+        current_player: PlayerId,
+        active: &HashSet<PlayerId>,
+        protected: &HashSet<PlayerId>,
+        no_target_allowed: bool,
+        violations: &mut Vec<Violation>,
+    ) {
+        if !self.target_required || no_target_allowed {
+            if action.has_target() {
+                violations.push(Violation::new(
+                    ActionPart::Target,
+                    Error::BadActionUnexpectedTarget,
+                ));
             }
-            if !self.current_allowed_as_target && current_player == target {
-                throw!(Error::BadActionCannotTargetSelf);
+            if action.has_second_target() {
+                violations.push(Violation::new(
+                    ActionPart::SecondTarget,
+                    Error::BadActionUnexpectedSecondTarget,
+                ));
             }
+            return;
+        }
+
+        let target = match action.target() {
+            Ok(target) => target,
+            Err(error) => {
+                violations.push(Violation::new(ActionPart::Target, error));
+                return;
+            }
+        };
+        self.check_target(
+            ActionPart::Target,
+            target,
+            current_player,
+            active,
+            protected,
+            violations,
+        );
+
+        if self.second_target != SecondTarget::NotAllowed {
+            if let Ok(second_target) = action.second_target() {
+                self.check_target(
+                    ActionPart::SecondTarget,
+                    second_target,
+                    current_player,
+                    active,
+                    protected,
+                    violations,
+                );
+                if second_target == target {
+                    violations.push(Violation::new(
+                        ActionPart::SecondTarget,
+                        Error::BadActionDuplicateTarget(target),
+                    ));
+                }
+            } else if self.second_target == SecondTarget::Required {
+                violations.push(Violation::new(
+                    ActionPart::SecondTarget,
+                    Error::BadActionMissingSecondTarget,
+                ));
+            }
+        } else if action.has_second_target() {
+            violations.push(Violation::new(
+                ActionPart::SecondTarget,
+                Error::BadActionUnexpectedSecondTarget,
+            ));
         }
     }
 
-    #[throws]
-    fn player_is_current(action: &CardAction, current_player: usize) {
+    fn check_player(
+        action: &CardAction,
+        current_player: PlayerId,
+        violations: &mut Vec<Violation>,
+    ) {
         if current_player != action.current() {
-            throw!(Error::BadActionNotCurrentPlayer(action.current()));
+            violations.push(Violation::new(
+                ActionPart::Player,
+                Error::BadActionNotCurrentPlayer(action.current()),
+            ));
         }
     }
 }
@@ -76,7 +333,7 @@ impl CardRules {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::state::Card;
+    use crate::state::OriginalCard;
     use std::iter::FromIterator;
 
     fn basic_rules() -> CardRules {
@@ -84,9 +341,23 @@ mod test {
             guess_required: false,
             current_allowed_as_target: false,
             target_required: false,
+            hand_constraint: HandConstraint::Unconstrained,
+            second_target: SecondTarget::NotAllowed,
+            forbidden_guess: None,
         }
     }
 
+    fn countess_required() -> CardRules {
+        basic_rules().must_play_instead("Countess")
+    }
+
+    fn hand(cards: &[OriginalCard]) -> Vec<Box<dyn Card>> {
+        cards
+            .iter()
+            .map(|c| Box::new(*c) as Box<dyn Card>)
+            .collect()
+    }
+
     fn target_required() -> CardRules {
         CardRules {
             target_required: true,
@@ -104,18 +375,29 @@ mod test {
     fn guess_required() -> CardRules {
         CardRules {
             guess_required: true,
-            ..basic_rules()
+            ..target_required()
         }
     }
 
     macro_rules! valid_action {
         (($card:expr, $current:expr, $target:expr, $guess:expr), $rules:expr, $actual_current:expr, $active:expr, $protected:expr) => {
+            valid_action!(
+                ($card, $current, $target, $guess),
+                $rules,
+                $actual_current,
+                $active,
+                $protected,
+                [$card]
+            );
+        };
+        (($card:expr, $current:expr, $target:expr, $guess:expr), $rules:expr, $actual_current:expr, $active:expr, $protected:expr, $hand:expr) => {
             assert_eq!(
                 $rules.action_allowed(
-                    &CardAction::new($card, $current, $target, $guess),
-                    $actual_current,
-                    &HashSet::from_iter((&$active).iter().cloned()),
-                    &HashSet::from_iter((&$protected).iter().cloned()),
+                    &CardAction::new($card, PlayerId($current), $target.map(PlayerId), $guess),
+                    PlayerId($actual_current),
+                    &HashSet::from_iter((&$active).iter().cloned().map(PlayerId)),
+                    &HashSet::from_iter((&$protected).iter().cloned().map(PlayerId)),
+                    &hand(&$hand),
                 )?,
                 ()
             );
@@ -124,13 +406,25 @@ mod test {
 
     macro_rules! invalid_action {
         (($card:expr, $current:expr, $target:expr, $guess:expr), $err:expr, $rules:expr, $actual_current:expr, $active:expr, $protected:expr) => {
+            invalid_action!(
+                ($card, $current, $target, $guess),
+                $err,
+                $rules,
+                $actual_current,
+                $active,
+                $protected,
+                [$card]
+            );
+        };
+        (($card:expr, $current:expr, $target:expr, $guess:expr), $err:expr, $rules:expr, $actual_current:expr, $active:expr, $protected:expr, $hand:expr) => {
             assert_eq!(
                 $rules
                     .action_allowed(
-                        &CardAction::new($card, $current, $target, $guess),
-                        $actual_current,
-                        &HashSet::from_iter((&$active).iter().cloned()),
-                        &HashSet::from_iter((&$protected).iter().cloned()),
+                        &CardAction::new($card, PlayerId($current), $target.map(PlayerId), $guess),
+                        PlayerId($actual_current),
+                        &HashSet::from_iter((&$active).iter().cloned().map(PlayerId)),
+                        &HashSet::from_iter((&$protected).iter().cloned().map(PlayerId)),
+                        &hand(&$hand),
                     )
                     .err(),
                 Some($err)
@@ -143,11 +437,17 @@ mod test {
     fn test_basic() {
         let rules = basic_rules();
 
-        valid_action!((Card::Princess, 0, None, None), rules, 0, [0, 1, 2, 3], []);
+        valid_action!(
+            (OriginalCard::Princess, 0, None, None),
+            rules,
+            0,
+            [0, 1, 2, 3],
+            []
+        );
 
         invalid_action!(
-            (Card::Princess, 1, None, None),
-            Error::BadActionNotCurrentPlayer(1),
+            (OriginalCard::Princess, 1, None, None),
+            Error::BadActionNotCurrentPlayer(PlayerId(1)),
             rules,
             0,
             [0, 1, 2, 3],
@@ -160,9 +460,15 @@ mod test {
     fn test_targets() {
         let rules = target_required();
 
-        valid_action!((Card::Baron, 0, Some(1), None), rules, 0, [0, 1, 2, 3], []);
         valid_action!(
-            (Card::Baron, 0, Some(1), None),
+            (OriginalCard::Baron, 0, Some(1), None),
+            rules,
+            0,
+            [0, 1, 2, 3],
+            []
+        );
+        valid_action!(
+            (OriginalCard::Baron, 0, Some(1), None),
             rules,
             0,
             [0, 1, 2, 3],
@@ -170,52 +476,52 @@ mod test {
         );
 
         invalid_action!(
-            (Card::Baron, 0, Some(1), None),
-            Error::BadActionNotCurrentPlayer(0),
+            (OriginalCard::Baron, 0, Some(1), None),
+            Error::BadActionNotCurrentPlayer(PlayerId(0)),
             rules,
             3,
             [0, 1, 2, 3],
             []
         );
         invalid_action!(
-            (Card::Baron, 0, Some(1), None),
-            Error::BadActionTargetingInactive(1),
+            (OriginalCard::Baron, 0, Some(1), None),
+            Error::BadActionTargetingInactive(PlayerId(1)),
             rules,
             0,
             [0, 2, 3],
             []
         );
         invalid_action!(
-            (Card::Baron, 0, Some(1), None),
-            Error::BadActionTargetingInactive(1),
+            (OriginalCard::Baron, 0, Some(1), None),
+            Error::BadActionTargetingInactive(PlayerId(1)),
             rules,
             0,
             [0, 2, 3],
             []
         );
         invalid_action!(
-            (Card::Baron, 0, Some(1), None),
-            Error::BadActionTargetingProtected(1),
+            (OriginalCard::Baron, 0, Some(1), None),
+            Error::BadActionTargetingProtected(PlayerId(1)),
             rules,
             0,
             [0, 1, 2, 3],
             [1]
         );
         invalid_action!(
-            (Card::Baron, 0, Some(0), None),
+            (OriginalCard::Baron, 0, Some(0), None),
             Error::BadActionCannotTargetSelf,
             rules,
             0,
             [0, 1, 2, 3],
             [1]
         );
-	valid_action!(
-	    (Card::Baron, 0, Some(2), None),
-	    rules,
-	    0,
-	    [0, 1, 2, 3],
-	    [1]
-	);
+        valid_action!(
+            (OriginalCard::Baron, 0, Some(2), None),
+            rules,
+            0,
+            [0, 1, 2, 3],
+            [1]
+        );
     }
 
     #[throws]
@@ -223,7 +529,13 @@ mod test {
     fn target_self() {
         let rules = current_allowed();
 
-        valid_action!((Card::Prince, 0, Some(0), None), rules, 0, [0, 1, 2, 3], []);
+        valid_action!(
+            (OriginalCard::Prince, 0, Some(0), None),
+            rules,
+            0,
+            [0, 1, 2, 3],
+            []
+        );
     }
 
     #[throws]
@@ -232,14 +544,14 @@ mod test {
         let rules = guess_required();
 
         valid_action!(
-            (Card::Guard, 0, Some(1), Some(Card::Baron)),
+            (OriginalCard::Guard, 0, Some(1), Some(OriginalCard::Baron)),
             rules,
             0,
             [0, 1, 2, 3],
             []
         );
         invalid_action!(
-            (Card::Guard, 0, Some(1), None),
+            (OriginalCard::Guard, 0, Some(1), None),
             Error::BadActionMissingGuess,
             rules,
             0,
@@ -247,4 +559,167 @@ mod test {
             []
         );
     }
+
+    #[test]
+    fn test_unexpected_target_and_guess() {
+        invalid_action!(
+            (OriginalCard::Handmaid, 0, Some(1), None),
+            Error::BadActionUnexpectedTarget,
+            basic_rules(),
+            0,
+            [0, 1, 2, 3],
+            []
+        );
+        invalid_action!(
+            (OriginalCard::Priest, 0, Some(1), Some(OriginalCard::Baron)),
+            Error::BadActionUnexpectedGuess,
+            target_required(),
+            0,
+            [0, 1, 2, 3],
+            []
+        );
+        let action = CardAction::new(OriginalCard::Priest, PlayerId(0), Some(PlayerId(1)), None)
+            .with_second_target(PlayerId(2));
+        let active = HashSet::from_iter((0..4).map(PlayerId));
+        assert_eq!(
+            Some(Error::BadActionUnexpectedSecondTarget),
+            target_required()
+                .action_allowed(
+                    &action,
+                    PlayerId(0),
+                    &active,
+                    &HashSet::new(),
+                    &hand(&[OriginalCard::Priest])
+                )
+                .err()
+        );
+    }
+
+    #[throws]
+    #[test]
+    fn test_must_play_instead() {
+        let rules = countess_required();
+
+        valid_action!(
+            (OriginalCard::King, 0, None, None),
+            rules,
+            0,
+            [0, 1, 2, 3],
+            [],
+            [OriginalCard::King, OriginalCard::Guard]
+        );
+        invalid_action!(
+            (OriginalCard::King, 0, None, None),
+            Error::BadActionMustPlayCard("Countess".to_string()),
+            rules,
+            0,
+            [0, 1, 2, 3],
+            [],
+            [OriginalCard::Countess, OriginalCard::King]
+        );
+    }
+
+    #[throws]
+    #[test]
+    fn test_no_valid_target() {
+        let rules = CardRules {
+            guess_required: true,
+            ..target_required()
+        };
+
+        valid_action!(
+            (OriginalCard::Guard, 0, None, None),
+            rules,
+            0,
+            [0, 1, 2],
+            [1, 2]
+        );
+        valid_action!((OriginalCard::Guard, 0, None, None), rules, 0, [0, 2], [2]);
+        invalid_action!(
+            (OriginalCard::Guard, 0, None, None),
+            Error::BadActionMissingTarget,
+            rules,
+            0,
+            [0, 1, 2],
+            [2]
+        );
+
+        let rules = current_allowed();
+        invalid_action!(
+            (OriginalCard::Prince, 0, None, None),
+            Error::BadActionMissingTarget,
+            rules,
+            0,
+            [0, 1, 2],
+            [1, 2]
+        );
+    }
+
+    #[throws]
+    #[test]
+    fn test_second_target() {
+        let required = target_required().with_second_target(SecondTarget::Required);
+        let optional = target_required().with_second_target(SecondTarget::Optional);
+        let active = HashSet::from_iter((0..4).map(PlayerId));
+        let protected = HashSet::from_iter(vec![PlayerId(3)]);
+        let hand = hand(&[OriginalCard::Baron]);
+        let baron = |second_target: Option<usize>| {
+            let action = CardAction::new(OriginalCard::Baron, PlayerId(0), Some(PlayerId(1)), None);
+            match second_target {
+                Some(second_target) => action.with_second_target(PlayerId(second_target)),
+                None => action,
+            }
+        };
+
+        required.action_allowed(&baron(Some(2)), PlayerId(0), &active, &protected, &hand)?;
+        optional.action_allowed(&baron(Some(2)), PlayerId(0), &active, &protected, &hand)?;
+        optional.action_allowed(&baron(None), PlayerId(0), &active, &protected, &hand)?;
+
+        let errors = vec![
+            (None, Error::BadActionMissingSecondTarget),
+            (Some(1), Error::BadActionDuplicateTarget(PlayerId(1))),
+            (Some(3), Error::BadActionTargetingProtected(PlayerId(3))),
+            (Some(0), Error::BadActionCannotTargetSelf),
+        ];
+        for (second_target, error) in errors {
+            assert_eq!(
+                Some(error),
+                required
+                    .action_allowed(
+                        &baron(second_target),
+                        PlayerId(0),
+                        &active,
+                        &protected,
+                        &hand
+                    )
+                    .err()
+            );
+        }
+    }
+
+    #[throws]
+    #[test]
+    fn test_forbidden_guess() {
+        let rules = CardRules {
+            guess_required: true,
+            ..target_required()
+        }
+        .forbid_guess("Guard");
+
+        valid_action!(
+            (OriginalCard::Guard, 0, Some(1), Some(OriginalCard::Priest)),
+            rules,
+            0,
+            [0, 1, 2, 3],
+            []
+        );
+        invalid_action!(
+            (OriginalCard::Guard, 0, Some(1), Some(OriginalCard::Guard)),
+            Error::BadActionInvalidGuess("Guard".to_string()),
+            rules,
+            0,
+            [0, 1, 2, 3],
+            []
+        );
+    }
 }
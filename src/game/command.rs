@@ -0,0 +1,302 @@
+//! A short text notation for actions, for consoles, chat bots and replays.
+//!
+//! A command is the card, then its targets, then its guess:
+//!
+//! ```text
+//! guard @2 priest
+//! prince self
+//! handmaid
+//! ```
+//!
+//! Cards may be abbreviated to any prefix that only one card starts with.
+//! A target is `self`, a seat number such as `@2`, or a player's name.
+//! Once a card has all its targets, the next word is its guess, even if a
+//! player has the same name.
+//! Words are not case sensitive, and spaces in names are written as `-`,
+//! or left out.
+
+use crate::game::card_action::CardAction;
+use crate::game::card_rules::SecondTarget;
+use crate::game::legal_actions::distinct;
+use crate::state::{Card, PlayerId, PremiumCard};
+use crate::{Error, Game};
+use fehler::{throw, throws};
+
+impl Game {
+    /// Reads a command for the current player.
+    #[throws]
+    pub fn parse_command(&self, command: &str) -> CardAction {
+        let mut words = command.split_whitespace();
+        let card_word = if let Some(word) = words.next() {
+            word
+        } else {
+            throw!(Error::CommandEmpty);
+        };
+
+        let mut deck = self.ruleset.deck();
+        let cards = distinct(&deck.deal(deck.cards_remaining()));
+        let card = find_card(&cards, card_word)?;
+        let rules = self.ruleset.rules_for_card(card.as_ref())?;
+        let max_targets = match (rules.target_required(), rules.second_target()) {
+            (false, _) => 0,
+            (true, SecondTarget::NotAllowed) => 1,
+            (true, _) => 2,
+        };
+
+        let by_value = PremiumCard::Bishop.is_same_card(card.as_ref());
+        let mut targets = Vec::new();
+        let mut guess = None;
+        for word in words {
+            // Once the card has all its targets, a word is read as a guess
+            // first, in case a player's name is also a card's name.
+            let wants_guess = rules.guess_required() && guess.is_none();
+            if wants_guess && targets.len() == max_targets && !word.starts_with('@') {
+                guess = Some(find_guess(&cards, word, by_value)?);
+                continue;
+            }
+            match self.find_player(word)? {
+                Some(player_id) if targets.len() < max_targets => targets.push(player_id),
+                None if wants_guess => guess = Some(find_guess(&cards, word, by_value)?),
+                _ => throw!(Error::CommandUnexpected(word.to_string())),
+            }
+        }
+
+        let has_guess = guess.is_some();
+        let action = CardAction::from_parts(
+            card,
+            self.current_player,
+            targets.first().copied(),
+            targets.get(1).copied(),
+            guess,
+        );
+        let name = action.card().name().to_string();
+        if targets.is_empty() {
+            // Without a target, the card is only playable if nobody can be
+            // targeted.
+            if rules.target_required() && self.is_valid_action(&action).is_err() {
+                throw!(Error::CommandNeedsTarget(name));
+            }
+            if has_guess {
+                throw!(Error::CommandNeedsTarget(name));
+            }
+        } else {
+            if targets.len() == 1 && *rules.second_target() == SecondTarget::Required {
+                throw!(Error::CommandNeedsSecondTarget(name));
+            }
+            if rules.guess_required() && !has_guess {
+                throw!(Error::CommandNeedsGuess(name));
+            }
+        }
+        action
+    }
+
+    /// Returns the player named by `word`, or None if `word` doesn't look
+    /// like a player.
+    #[throws]
+    fn find_player(&self, word: &str) -> Option<PlayerId> {
+        if word.eq_ignore_ascii_case("self") {
+            return Some(self.current_player);
+        }
+        let (word, must_be_player) = if let Some(rest) = word.strip_prefix('@') {
+            (rest, true)
+        } else {
+            (word, false)
+        };
+
+        let seat = word
+            .parse::<usize>()
+            .ok()
+            .filter(|seat| must_be_player && *seat >= 1);
+        let found = self.table.player_ids().find(|id| {
+            Some(id.index() + 1) == seat || normalize(&self.name_of(*id)) == normalize(word)
+        });
+        if found.is_none() && must_be_player {
+            throw!(Error::CommandUnknownPlayer(word.to_string()));
+        }
+        found
+    }
+}
+
+impl CardAction {
+    /// The action as a command that `Game::parse_command` reads back to the
+    /// same action. Targets are written as seat numbers.
+    pub fn to_command(&self) -> String {
+        let mut words = vec![card_word(self.card())];
+        let targets = self
+            .target()
+            .ok()
+            .into_iter()
+            .chain(self.second_target().ok());
+        for target in targets {
+            if target == self.current() {
+                words.push("self".to_string());
+            } else {
+                words.push(format!("@{}", target.index() + 1));
+            }
+        }
+        if let Ok(guess) = self.guess() {
+            words.push(card_word(guess));
+        }
+        words.join(" ")
+    }
+}
+
+/// Lower case, without spaces or dashes.
+fn normalize(word: &str) -> String {
+    word.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn card_word(card: &dyn Card) -> String {
+    card.name().to_lowercase().replace(' ', "-")
+}
+
+#[throws]
+fn find_card(cards: &[Box<dyn Card>], word: &str) -> Box<dyn Card> {
+    let word = normalize(word);
+    if let Some(card) = cards.iter().find(|c| normalize(c.name()) == word) {
+        return card.clone();
+    }
+    let matches = cards
+        .iter()
+        .filter(|c| normalize(c.name()).starts_with(&word))
+        .collect::<Vec<_>>();
+    match matches.as_slice() {
+        [] => throw!(Error::CommandUnknownCard(word)),
+        [card] => (*card).clone(),
+        _ => throw!(Error::CommandAmbiguousCard(
+            word,
+            matches.iter().map(|c| c.name().to_string()).collect()
+        )),
+    }
+}
+
+/// A guess may be a card, or the value of a card. A value that more than
+/// one card has is ambiguous, unless the card only guesses the value.
+#[throws]
+fn find_guess(cards: &[Box<dyn Card>], word: &str, by_value: bool) -> Box<dyn Card> {
+    if let Ok(value) = word.parse::<u8>() {
+        let matches = cards
+            .iter()
+            .filter(|c| c.value() == value)
+            .collect::<Vec<_>>();
+        match matches.as_slice() {
+            [] => throw!(Error::CommandUnknownCard(word.to_string())),
+            [card] => return (*card).clone(),
+            [card, ..] if by_value => return (*card).clone(),
+            _ => throw!(Error::CommandAmbiguousCard(
+                word.to_string(),
+                matches.iter().map(|c| c.name().to_string()).collect()
+            )),
+        }
+    }
+    find_card(cards, word)?
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::test::{deck, original};
+    use crate::game::{GameBuilder, PremiumRuleset};
+    use crate::state::{OriginalCard::*, Table};
+
+    fn game() -> Game {
+        let table = Table::with_names(&["Ann", "Bo", "Cy"]).unwrap();
+        Game::with_stacked_deck(
+            original(),
+            table,
+            PlayerId(0),
+            deck(&[Guard, Priest, Baron, Countess, Prince]),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    #[throws]
+    fn test_parse_command() {
+        let game = game();
+        let action = game.parse_command("guard @2 priest")?;
+        assert!(Guard.is_same_card(action.card()));
+        assert_eq!(PlayerId(0), action.current());
+        assert_eq!(PlayerId(1), action.target()?);
+        assert!(Priest.is_same_card(action.guess()?));
+
+        let action = game.parse_command("  GU   cy 2 ")?;
+        assert_eq!(PlayerId(2), action.target()?);
+        assert!(Priest.is_same_card(action.guess()?));
+
+        let action = game.parse_command("prince self")?;
+        assert_eq!(PlayerId(0), action.target()?);
+        let action = game.parse_command("ki @Bo")?;
+        assert!(King.is_same_card(action.card()));
+        assert_eq!(PlayerId(1), action.target()?);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let game = game();
+        let error = |command| game.parse_command(command).err().unwrap().to_string();
+        assert_eq!("Guard needs a guess", error("guard @2"));
+        assert_eq!("Guard needs a target", error("guard"));
+        assert_eq!("Enter a card to play", error(""));
+        assert_eq!("No card is called jack", error("jack"));
+        assert_eq!("prin could be Prince or Princess", error("prin @2"));
+        assert_eq!("No player is called 4", error("guard @4 priest"));
+        assert_eq!("Did not expect @3", error("handmaid @3"));
+        assert_eq!("Did not expect baron", error("guard @2 priest baron"));
+    }
+
+    #[test]
+    #[throws]
+    fn test_player_named_like_card() {
+        let table = Table::with_names(&["Ann", "Priest", "Cy"])?;
+        let game = Game::with_stacked_deck(
+            original(),
+            table,
+            PlayerId(0),
+            deck(&[Guard, Priest, Baron, Countess, Prince]),
+        )?;
+        for command in &["guard @2 priest", "guard priest priest", "guard @priest 2"] {
+            let action = game.parse_command(command)?;
+            assert_eq!(PlayerId(1), action.target()?);
+            assert!(Priest.is_same_card(action.guess()?));
+        }
+    }
+
+    #[test]
+    #[throws]
+    fn test_parse_ambiguous_value() {
+        let game = GameBuilder::new()
+            .num_players(5)
+            .ruleset(PremiumRuleset)
+            .seed(2)
+            .build()?;
+        assert_eq!(
+            "7 could be Dowager Queen or Countess",
+            game.parse_command("guard @2 7").err().unwrap().to_string()
+        );
+        let action = game.parse_command("bishop @2 7")?;
+        assert_eq!(7, action.guess()?.value());
+    }
+
+    #[test]
+    #[throws]
+    fn test_to_command() {
+        let game = game();
+        for command in &["guard @2 priest", "prince self", "handmaid", "king @3"] {
+            assert_eq!(*command, game.parse_command(command)?.to_command());
+        }
+
+        let game = GameBuilder::new()
+            .num_players(5)
+            .ruleset(PremiumRuleset)
+            .seed(2)
+            .build()?;
+        for action in game.legal_actions(game.current_player())? {
+            let command = action.to_command();
+            assert_eq!(command, game.parse_command(&command)?.to_command());
+        }
+    }
+}
@@ -0,0 +1,477 @@
+use crate::game::RoundOutcome;
+use crate::messenger::Messenger;
+use crate::state::{Card, OriginalCard, PlayerId, PremiumCard};
+use crate::Game;
+
+/// Who may see an event.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Audience {
+    All,
+    Player(PlayerId),
+}
+
+/// Why a player was knocked out of the round.
+#[derive(Clone, Debug)]
+pub enum EliminationReason {
+    /// Another player correctly guessed their card.
+    CardGuessed(Box<dyn Card>),
+    /// They showed this card and it was lower than their opponent's, or
+    /// higher, for the Dowager Queen.
+    LostComparison(Box<dyn Card>),
+    /// They were made to discard the Princess.
+    PrincessDiscarded,
+    /// They played the Princess.
+    PrincessPlayed,
+    /// They played a Guard on the player who held the Assassin.
+    Assassin(PlayerId),
+}
+
+/// Something that happened in a game.
+///
+/// Every change that a player could see at the table is reported as an
+/// event, so programs don't have to parse the text sent by a `Messenger`.
+#[derive(Clone, Debug)]
+pub enum GameEvent {
+    /// The player discarded the card that they chose to play.
+    CardDiscarded {
+        player: PlayerId,
+        card: Box<dyn Card>,
+    },
+    /// The card's effect is applied to the targets, if it has any.
+    CardPlayed {
+        player: PlayerId,
+        card: Box<dyn Card>,
+        targets: Vec<PlayerId>,
+    },
+    /// Every other player was protected, so the card does nothing.
+    NoTarget {
+        player: PlayerId,
+        card: Box<dyn Card>,
+    },
+    /// A guess at another player's card. Some cards guess only the value.
+    GuessMade {
+        player: PlayerId,
+        card: Box<dyn Card>,
+        target: PlayerId,
+        guess: Box<dyn Card>,
+        by_value: bool,
+    },
+    GuessMissed {
+        target: PlayerId,
+        guess: Box<dyn Card>,
+        by_value: bool,
+    },
+    /// A correct guess that earns the guesser a token, rather than
+    /// knocking out the target.
+    GuessRewarded {
+        player: PlayerId,
+        target: PlayerId,
+        guess: Box<dyn Card>,
+        by_value: bool,
+    },
+    /// The owner shows their hand to the viewer, who played the card.
+    HandShown {
+        owner: PlayerId,
+        viewer: PlayerId,
+        card: Box<dyn Card>,
+    },
+    /// The card that the viewer sees in the owner's hand.
+    CardRevealed {
+        owner: PlayerId,
+        viewer: PlayerId,
+        card: Box<dyn Card>,
+    },
+    /// The card that the viewer sees in the owner's hand after a trade.
+    CardLookedAt {
+        owner: PlayerId,
+        viewer: PlayerId,
+        card: Box<dyn Card>,
+    },
+    /// Two hands were compared. If neither was out, the cards were equal.
+    CardsCompared {
+        player: PlayerId,
+        card: Box<dyn Card>,
+        target: PlayerId,
+        loser: Option<PlayerId>,
+    },
+    PlayerProtected {
+        player: PlayerId,
+    },
+    PlayerEliminated {
+        player: PlayerId,
+        reason: EliminationReason,
+    },
+    /// The player was made to discard their hand.
+    HandDiscarded {
+        player: PlayerId,
+        card: Box<dyn Card>,
+    },
+    /// The card that a player drew to replace a discarded hand.
+    CardDrawn {
+        player: PlayerId,
+        card: Box<dyn Card>,
+    },
+    /// The card that a player draws at the start of their turn.
+    CardDealt {
+        player: PlayerId,
+        card: Box<dyn Card>,
+    },
+    HandsSwapped {
+        first: PlayerId,
+        second: PlayerId,
+    },
+    /// The card that a player received in a trade.
+    CardReceived {
+        player: PlayerId,
+        from: PlayerId,
+        card: Box<dyn Card>,
+    },
+    /// The next card played must target this player, if it can.
+    ForcedTargetSet {
+        target: PlayerId,
+    },
+    /// The player drew extra cards, and must return as many to the deck.
+    CardsDrawnToReturn {
+        player: PlayerId,
+        card: Box<dyn Card>,
+        count: usize,
+    },
+    CardsReturned {
+        player: PlayerId,
+        count: usize,
+    },
+    RoundWon {
+        outcome: RoundOutcome,
+    },
+    TokenAwarded {
+        player: PlayerId,
+        tokens: usize,
+    },
+    MatchWon {
+        player: PlayerId,
+    },
+}
+
+impl GameEvent {
+    /// The players who may see the event.
+    pub fn audience(&self) -> Audience {
+        match self {
+            GameEvent::CardRevealed { viewer, .. } | GameEvent::CardLookedAt { viewer, .. } => {
+                Audience::Player(*viewer)
+            }
+            GameEvent::CardDrawn { player, .. }
+            | GameEvent::CardDealt { player, .. }
+            | GameEvent::CardReceived { player, .. } => Audience::Player(*player),
+            _ => Audience::All,
+        }
+    }
+
+    /// Returns true if the event showed someone a card that was hidden from
+    /// them. An eliminated player's hand is discarded face up, so every
+    /// elimination counts.
+    pub fn reveals_hidden_info(&self) -> bool {
+        match self {
+            GameEvent::PlayerEliminated { .. } | GameEvent::CardsCompared { .. } => true,
+            _ => self.audience() != Audience::All,
+        }
+    }
+
+    /// Describes the event in English, using `name_of` to name players.
+    /// Returns None if the event is already described by the events
+    /// around it.
+    pub fn describe(&self, name_of: impl Fn(PlayerId) -> String) -> Option<String> {
+        let guessed = |guess: &dyn Card, by_value: bool| {
+            if by_value {
+                guess.value().to_string()
+            } else {
+                guess.to_string()
+            }
+        };
+        let text = match self {
+            GameEvent::CardDiscarded { player, card } => {
+                format!("{} discards a {}", name_of(*player), card)
+            }
+            GameEvent::CardPlayed {
+                player,
+                card,
+                targets,
+            } => {
+                let mut text = format!("{} plays a {}", name_of(*player), card);
+                if !targets.is_empty() {
+                    let names = targets.iter().map(|t| name_of(*t)).collect::<Vec<_>>();
+                    text = format!("{} on {}", text, names.join(" and "));
+                }
+                text
+            }
+            GameEvent::NoTarget { player, card } => format!(
+                "{} plays a {}, but there is no one to target. It has no effect.",
+                name_of(*player),
+                card
+            ),
+            // The editions word some messages differently, and the text is
+            // kept as each edition first sent it.
+            GameEvent::GuessMade {
+                player,
+                card,
+                target,
+                guess,
+                by_value,
+            } if PremiumCard::Bishop.is_same_card(card.as_ref()) => format!(
+                "{} plays a Bishop and guesses that {} has a {}",
+                name_of(*player),
+                name_of(*target),
+                guessed(guess.as_ref(), *by_value)
+            ),
+            GameEvent::GuessMade {
+                player,
+                target,
+                guess,
+                by_value,
+                ..
+            } => format!(
+                "{} guesses that {} has a {}",
+                name_of(*player),
+                name_of(*target),
+                guessed(guess.as_ref(), *by_value)
+            ),
+            GameEvent::GuessMissed {
+                target,
+                guess,
+                by_value,
+            } => format!(
+                "{} does not have a {}",
+                name_of(*target),
+                guessed(guess.as_ref(), *by_value)
+            ),
+            GameEvent::GuessRewarded {
+                player,
+                target,
+                guess,
+                by_value,
+            } => format!(
+                "{} has a {}. {} gains a token of affection!",
+                name_of(*target),
+                guessed(guess.as_ref(), *by_value),
+                name_of(*player)
+            ),
+            GameEvent::HandShown {
+                owner,
+                viewer,
+                card,
+            } if OriginalCard::Priest.is_same_card(card.as_ref()) => {
+                format!("{} shows his card to {}", name_of(*owner), name_of(*viewer))
+            }
+            GameEvent::HandShown { owner, viewer, .. } => format!(
+                "{} shows their card to {}",
+                name_of(*owner),
+                name_of(*viewer)
+            ),
+            GameEvent::CardRevealed { owner, card, .. } => {
+                format!("{} shows you a {}", name_of(*owner), card)
+            }
+            GameEvent::CardLookedAt { owner, card, .. } => {
+                format!("{} now has a {}", name_of(*owner), card)
+            }
+            GameEvent::CardsCompared {
+                card, loser: None, ..
+            } if PremiumCard::DowagerQueen.is_same_card(card.as_ref()) => {
+                "The cards are equal. Nobody is out.".to_string()
+            }
+            GameEvent::CardsCompared { loser: None, .. } => {
+                "Boingy, boingy, boingy. (The cards are equal.)".to_string()
+            }
+            // The loser's elimination says it all.
+            GameEvent::CardsCompared { loser: Some(_), .. } => return None,
+            GameEvent::PlayerProtected { player } => format!("{} is safe.", name_of(*player)),
+            GameEvent::PlayerEliminated { player, reason } => {
+                let player = name_of(*player);
+                match reason {
+                    EliminationReason::CardGuessed(card) => {
+                        format!("{} has a {} and is out!", player, card)
+                    }
+                    EliminationReason::LostComparison(card) => {
+                        format!("{} showed a {} and is out.", player, card)
+                    }
+                    EliminationReason::PrincessDiscarded => format!("{} is out!", player),
+                    EliminationReason::PrincessPlayed => {
+                        format!("{} discarded the Princess and is out!", player)
+                    }
+                    EliminationReason::Assassin(assassin) => {
+                        format!(
+                            "{} has the Assassin! {} is out!",
+                            name_of(*assassin),
+                            player
+                        )
+                    }
+                }
+            }
+            GameEvent::HandDiscarded { player, card } => {
+                format!("{} discards a {}.", name_of(*player), card)
+            }
+            GameEvent::CardDrawn { card, .. } => format!("You draw a {}.", card),
+            // The new card is in the player's hand, so it has never had a
+            // message of its own.
+            GameEvent::CardDealt { .. } => return None,
+            GameEvent::HandsSwapped { first, second } => {
+                format!("{} and {} trade hands.", name_of(*first), name_of(*second))
+            }
+            GameEvent::CardReceived { from, card, .. } => {
+                format!("{} gives you a {}.", name_of(*from), card)
+            }
+            GameEvent::ForcedTargetSet { target } => format!(
+                "The next card played must choose {}, if it can.",
+                name_of(*target)
+            ),
+            GameEvent::CardsDrawnToReturn {
+                player,
+                card,
+                count,
+            } => {
+                if *count == 0 {
+                    format!("The deck is empty, so the {} has no effect.", card)
+                } else {
+                    format!(
+                        "{} draws {} cards and must return {} of them.",
+                        name_of(*player),
+                        count,
+                        count
+                    )
+                }
+            }
+            GameEvent::CardsReturned { player, count } => format!(
+                "{} puts {} cards on the bottom of the deck.",
+                name_of(*player),
+                count
+            ),
+            GameEvent::RoundWon { outcome } => outcome.describe(name_of),
+            GameEvent::TokenAwarded { player, tokens } => format!(
+                "{} receives a token of affection and now has {}.",
+                name_of(*player),
+                tokens
+            ),
+            GameEvent::MatchWon { player } => format!("{} wins the match!", name_of(*player)),
+        };
+        Some(text)
+    }
+
+    /// Sends the event's description, if it has one, to its audience.
+    pub fn send_text<M: Messenger + ?Sized>(&self, game: &Game, messenger: &mut M) {
+        if let Some(text) = self.describe(|id| game.name_of(id).into_owned()) {
+            match self.audience() {
+                Audience::All => messenger.to_all(&text),
+                Audience::Player(id) => {
+                    if let Ok(player) = game.player(id) {
+                        messenger.to_player(player, &text);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_describe() {
+        let name_of = |id: PlayerId| format!("P{}", id.index());
+        let played = GameEvent::CardPlayed {
+            player: PlayerId(0),
+            card: Box::new(OriginalCard::King),
+            targets: vec![PlayerId(1), PlayerId(2)],
+        };
+        assert_eq!(
+            Some("P0 plays a King on P1 and P2".to_string()),
+            played.describe(name_of)
+        );
+        assert_eq!(Audience::All, played.audience());
+
+        let revealed = GameEvent::CardRevealed {
+            owner: PlayerId(1),
+            viewer: PlayerId(0),
+            card: Box::new(OriginalCard::Baron),
+        };
+        assert_eq!(
+            Some("P1 shows you a Baron".to_string()),
+            revealed.describe(name_of)
+        );
+        assert_eq!(Audience::Player(PlayerId(0)), revealed.audience());
+
+        let guess = GameEvent::GuessMade {
+            player: PlayerId(0),
+            card: Box::new(PremiumCard::Guard),
+            target: PlayerId(1),
+            guess: Box::new(OriginalCard::Priest),
+            by_value: true,
+        };
+        assert_eq!(
+            Some("P0 guesses that P1 has a 2".to_string()),
+            guess.describe(name_of)
+        );
+
+        let compared = GameEvent::CardsCompared {
+            player: PlayerId(0),
+            card: Box::new(OriginalCard::Baron),
+            target: PlayerId(1),
+            loser: Some(PlayerId(1)),
+        };
+        assert_eq!(None, compared.describe(name_of));
+    }
+
+    #[test]
+    fn test_describe_edition_wording() {
+        let name_of = |id: PlayerId| format!("P{}", id.index());
+        let shown = GameEvent::HandShown {
+            owner: PlayerId(1),
+            viewer: PlayerId(0),
+            card: Box::new(OriginalCard::Priest),
+        };
+        assert_eq!(
+            Some("P1 shows his card to P0".to_string()),
+            shown.describe(name_of)
+        );
+        let shown = GameEvent::HandShown {
+            owner: PlayerId(1),
+            viewer: PlayerId(0),
+            card: Box::new(PremiumCard::Baroness),
+        };
+        assert_eq!(
+            Some("P1 shows their card to P0".to_string()),
+            shown.describe(name_of)
+        );
+
+        let compared = GameEvent::CardsCompared {
+            player: PlayerId(0),
+            card: Box::new(PremiumCard::DowagerQueen),
+            target: PlayerId(1),
+            loser: None,
+        };
+        assert_eq!(
+            Some("The cards are equal. Nobody is out.".to_string()),
+            compared.describe(name_of)
+        );
+        let compared = GameEvent::CardsCompared {
+            player: PlayerId(0),
+            card: Box::new(OriginalCard::Baron),
+            target: PlayerId(1),
+            loser: None,
+        };
+        assert_eq!(
+            Some("Boingy, boingy, boingy. (The cards are equal.)".to_string()),
+            compared.describe(name_of)
+        );
+
+        let guess = GameEvent::GuessMade {
+            player: PlayerId(0),
+            card: Box::new(PremiumCard::Bishop),
+            target: PlayerId(1),
+            guess: Box::new(PremiumCard::King),
+            by_value: true,
+        };
+        assert_eq!(
+            Some("P0 plays a Bishop and guesses that P1 has a 6".to_string()),
+            guess.describe(name_of)
+        );
+    }
+}
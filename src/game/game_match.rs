@@ -0,0 +1,408 @@
+use crate::game::card_action::CardAction;
+use crate::game::game_event::GameEvent;
+use crate::game::ruleset::Ruleset;
+use crate::messenger::Messenger;
+use crate::state::{Card, PlayerId};
+use crate::{Error, Game};
+use fehler::{throw, throws};
+use rand::RngCore;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+/// A match is a series of rounds. The winner of each round receives a
+/// token of affection, and the first player to collect enough tokens
+/// wins the match.
+pub struct Match {
+    ruleset: Rc<dyn Ruleset>,
+    names: Vec<String>,
+    rng: Box<dyn RngCore>,
+    game: Game,
+    tokens: Vec<usize>,
+    /// How many of the round's bonus tokens have been counted.
+    bonus_tokens_counted: usize,
+    round_number: usize,
+}
+
+impl Match {
+    #[throws]
+    pub(crate) fn new(
+        ruleset: Rc<dyn Ruleset>,
+        names: Vec<String>,
+        first_player: PlayerId,
+        mut rng: Box<dyn RngCore>,
+    ) -> Match {
+        Match {
+            game: Game::from_seed(Rc::clone(&ruleset), &names, first_player, rng.next_u64())?,
+            ruleset,
+            tokens: vec![0; names.len()],
+            bonus_tokens_counted: 0,
+            names,
+            rng,
+            round_number: 1,
+        }
+    }
+
+    /// The round currently being played.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// The number of the current round, starting from 1.
+    pub fn round_number(&self) -> usize {
+        self.round_number
+    }
+
+    /// The number of tokens of affection that the player has collected.
+    #[throws]
+    pub fn tokens(&self, player_id: PlayerId) -> usize {
+        if let Some(tokens) = self.tokens.get(player_id.index()) {
+            *tokens
+        } else {
+            throw!(Error::InvalidPlayerNumber(player_id));
+        }
+    }
+
+    /// The number of tokens needed to win the match.
+    pub fn tokens_to_win(&self) -> usize {
+        self.ruleset.tokens_to_win(self.names.len())
+    }
+
+    /// The player who won the match, if the match is over.
+    ///
+    /// If more than one player has enough tokens, the one with the most
+    /// wins. If they are tied for the most, nobody has won yet, and rounds
+    /// are played until one of them leads.
+    pub fn winner(&self) -> Option<PlayerId> {
+        let most = *self.tokens.iter().max()?;
+        if most < self.tokens_to_win() {
+            return None;
+        }
+        let mut leaders = self.tokens.iter().enumerate().filter(|(_, t)| **t == most);
+        match (leaders.next(), leaders.next()) {
+            (Some((index, _)), None) => Some(PlayerId(index)),
+            _ => None,
+        }
+    }
+
+    /// Performs the action in the current round. If that ends the round,
+    /// the round's winners are awarded their tokens. A token that a card
+    /// awards during the round, like the Bishop's, is counted at once, so
+    /// the match may end before the round does.
+    #[throws]
+    pub fn perform_action(&mut self, action: CardAction, messenger: &mut impl Messenger) {
+        if self.winner().is_some() {
+            throw!(Error::MatchOver);
+        }
+
+        self.game.perform_action(action, messenger)?;
+        self.award_tokens(messenger);
+    }
+
+    /// Puts cards back on the bottom of the deck after a card like the
+    /// Chancellor, as `Game::return_cards` does. If that ends the round,
+    /// the round's winners are awarded their tokens.
+    #[throws]
+    pub fn return_cards<C: Card + Clone + 'static>(
+        &mut self,
+        cards: &[C],
+        messenger: &mut impl Messenger,
+    ) {
+        if self.winner().is_some() {
+            throw!(Error::MatchOver);
+        }
+
+        self.game.return_cards(cards, messenger)?;
+        self.award_tokens(messenger);
+    }
+
+    /// Awards the bonus tokens that haven't been counted yet, and the round
+    /// winners' tokens if the round is over.
+    fn award_tokens(&mut self, messenger: &mut impl Messenger) {
+        let mut awarded = Vec::new();
+        if let Some(outcome) = self.game.round_outcome() {
+            awarded.extend_from_slice(outcome.winners());
+        }
+        let bonus_tokens = self.game.bonus_tokens();
+        awarded.extend_from_slice(&bonus_tokens[self.bonus_tokens_counted..]);
+        self.bonus_tokens_counted = bonus_tokens.len();
+
+        for player_id in &awarded {
+            self.tokens[player_id.index()] += 1;
+            messenger.event(
+                &self.game,
+                &GameEvent::TokenAwarded {
+                    player: *player_id,
+                    tokens: self.tokens[player_id.index()],
+                },
+            );
+        }
+        if !awarded.is_empty() {
+            if let Some(winner) = self.winner() {
+                messenger.event(&self.game, &GameEvent::MatchWon { player: winner });
+            }
+        }
+    }
+
+    /// Starts the next round with a freshly shuffled deck. The winner of
+    /// the previous round goes first.
+    #[throws]
+    pub fn start_next_round(&mut self) {
+        if self.winner().is_some() {
+            throw!(Error::MatchOver);
+        }
+
+        let first_player = if let Some(outcome) = self.game.round_outcome() {
+            outcome.winners()[0]
+        } else {
+            throw!(Error::RoundNotOver);
+        };
+
+        self.game = Game::from_seed(
+            Rc::clone(&self.ruleset),
+            &self.names,
+            first_player,
+            self.rng.next_u64(),
+        )?;
+        self.bonus_tokens_counted = 0;
+        self.round_number += 1;
+    }
+}
+
+impl Debug for Match {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // The random number generator can't be printed.
+        f.debug_struct("Match")
+            .field("ruleset", &self.ruleset)
+            .field("names", &self.names)
+            .field("game", &self.game)
+            .field("tokens", &self.tokens)
+            .field("bonus_tokens_counted", &self.bonus_tokens_counted)
+            .field("round_number", &self.round_number)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::test::{deck, original};
+    use crate::game::{PremiumRuleset, RevisedRuleset};
+    use crate::messenger::test::TestMessenger;
+    use crate::state::{seeded_rng, Deck, OriginalCard, PremiumCard, RevisedCard};
+
+    fn names(num_players: usize) -> Vec<String> {
+        (0..num_players).map(|i| PlayerId(i).to_string()).collect()
+    }
+
+    fn rigged_round(num_players: usize, first_player: usize, draws: &[OriginalCard]) -> Game {
+        use OriginalCard::*;
+        // The first player holds a Guard, the next player holds a Priest,
+        // and everyone else holds a Countess. A Baron is set aside.
+        let mut cards = vec![Countess; num_players];
+        cards[first_player] = Guard;
+        cards[(first_player + 1) % num_players] = Priest;
+        cards.push(Baron);
+        cards.extend_from_slice(draws);
+        Game::with_deck(
+            original(),
+            num_players,
+            PlayerId(first_player),
+            Deck::new(cards.into_iter().map(|c| Box::new(c) as _).collect()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tokens_to_win() {
+        assert_eq!(
+            7,
+            Match::new(original(), names(2), PlayerId(0), Box::new(seeded_rng(0)))
+                .unwrap()
+                .tokens_to_win()
+        );
+        assert_eq!(
+            5,
+            Match::new(original(), names(3), PlayerId(0), Box::new(seeded_rng(0)))
+                .unwrap()
+                .tokens_to_win()
+        );
+        assert_eq!(
+            4,
+            Match::new(original(), names(4), PlayerId(0), Box::new(seeded_rng(0)))
+                .unwrap()
+                .tokens_to_win()
+        );
+    }
+
+    #[test]
+    #[throws]
+    fn test_round_awards_token() {
+        use OriginalCard::*;
+        let mut m = Match::new(original(), names(3), PlayerId(0), Box::new(seeded_rng(0)))?;
+        m.game = rigged_round(3, 0, &[Handmaid, Handmaid, King]);
+        let mut messenger = TestMessenger::new();
+
+        assert_eq!(Some(Error::RoundNotOver), m.start_next_round().err());
+
+        m.perform_action(
+            CardAction::new(Guard, PlayerId(0), Some(PlayerId(1)), Some(Priest)),
+            &mut messenger,
+        )?;
+        m.perform_action(
+            CardAction::new(Handmaid, PlayerId(2), None, None),
+            &mut messenger,
+        )?;
+        m.perform_action(
+            CardAction::new(Handmaid, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+
+        // The deck is empty. Player 0 has a King and player 2 has a Countess.
+        assert_eq!(0, m.tokens(PlayerId(0))?);
+        assert_eq!(1, m.tokens(PlayerId(2))?);
+        assert_eq!(None, m.winner());
+
+        m.start_next_round()?;
+        assert_eq!(2, m.round_number());
+        assert_eq!(PlayerId(2), m.game().current_player());
+        assert!(!m.game().is_round_over());
+    }
+
+    #[test]
+    #[throws]
+    fn test_match_winner() {
+        use OriginalCard::*;
+        let mut m = Match::new(original(), names(4), PlayerId(0), Box::new(seeded_rng(0)))?;
+        m.tokens = vec![3, 0, 0, 0];
+        m.game = rigged_round(4, 2, &[Handmaid, Handmaid, Handmaid]);
+        let mut messenger = TestMessenger::new();
+
+        m.perform_action(
+            CardAction::new(Guard, PlayerId(2), Some(PlayerId(3)), Some(Priest)),
+            &mut messenger,
+        )?;
+        m.perform_action(
+            CardAction::new(Handmaid, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        m.perform_action(
+            CardAction::new(Handmaid, PlayerId(1), None, None),
+            &mut messenger,
+        )?;
+
+        // Players 0 and 1 tie with a Countess and a discarded Handmaid,
+        // so they both receive a token.
+        assert_eq!(4, m.tokens(PlayerId(0))?);
+        assert_eq!(1, m.tokens(PlayerId(1))?);
+        assert_eq!(Some(PlayerId(0)), m.winner());
+        assert_eq!(Some(Error::MatchOver), m.start_next_round().err());
+    }
+
+    #[test]
+    #[throws]
+    fn test_return_cards() {
+        use RevisedCard::*;
+        let ruleset: Rc<dyn Ruleset> = Rc::new(RevisedRuleset);
+        let mut m = Match::new(
+            Rc::clone(&ruleset),
+            names(3),
+            PlayerId(0),
+            Box::new(seeded_rng(0)),
+        )?;
+        m.game = Game::with_deck(
+            ruleset,
+            3,
+            PlayerId(0),
+            deck(&[Chancellor, Guard, Priest, Baron, Handmaid, Spy]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        m.perform_action(
+            CardAction::new(Chancellor, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        m.return_cards(&[Handmaid], &mut messenger)?;
+        m.perform_action(
+            CardAction::new(Handmaid, PlayerId(1), None, None),
+            &mut messenger,
+        )?;
+
+        // The deck is empty, and player 2's Priest beats player 0's Spy and
+        // player 1's Guard.
+        assert!(m.game().is_round_over());
+        assert_eq!(1, m.tokens(PlayerId(2))?);
+        m.start_next_round()?;
+        assert_eq!(PlayerId(2), m.game().current_player());
+    }
+
+    #[test]
+    #[throws]
+    fn test_bishop_ends_match() {
+        use PremiumCard::*;
+        let ruleset: Rc<dyn Ruleset> = Rc::new(PremiumRuleset);
+        let mut m = Match::new(
+            Rc::clone(&ruleset),
+            names(5),
+            PlayerId(0),
+            Box::new(seeded_rng(0)),
+        )?;
+        m.tokens = vec![2, 0, 0, 0, 0];
+        m.game = Game::with_deck(
+            ruleset,
+            5,
+            PlayerId(0),
+            deck(&[
+                Bishop, Priest, Baron, King, Baron, Countess, Handmaid, Guard, Guard,
+            ]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        m.perform_action(
+            CardAction::new(Bishop, PlayerId(0), Some(PlayerId(3)), Some(King)),
+            &mut messenger,
+        )?;
+        assert!(!m.game().is_round_over());
+        assert_eq!(3, m.tokens(PlayerId(0))?);
+        assert_eq!(Some(PlayerId(0)), m.winner());
+        assert_eq!(
+            Some(Error::MatchOver),
+            m.perform_action(
+                CardAction::new(Guard, PlayerId(1), Some(PlayerId(2)), Some(Priest)),
+                &mut messenger
+            )
+            .err()
+        );
+    }
+
+    #[test]
+    #[throws]
+    fn test_tied_winners_play_on() {
+        use OriginalCard::*;
+        let mut m = Match::new(original(), names(4), PlayerId(0), Box::new(seeded_rng(0)))?;
+        m.tokens = vec![3, 3, 0, 0];
+        m.game = rigged_round(4, 2, &[Handmaid, Handmaid, Handmaid]);
+        let mut messenger = TestMessenger::new();
+
+        m.perform_action(
+            CardAction::new(Guard, PlayerId(2), Some(PlayerId(3)), Some(Priest)),
+            &mut messenger,
+        )?;
+        m.perform_action(
+            CardAction::new(Handmaid, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        m.perform_action(
+            CardAction::new(Handmaid, PlayerId(1), None, None),
+            &mut messenger,
+        )?;
+
+        // Players 0 and 1 both reach 4 tokens, so neither has won yet.
+        assert_eq!(4, m.tokens(PlayerId(0))?);
+        assert_eq!(4, m.tokens(PlayerId(1))?);
+        assert_eq!(None, m.winner());
+        m.start_next_round()?;
+
+        m.tokens = vec![5, 4, 0, 0];
+        assert_eq!(Some(PlayerId(0)), m.winner());
+    }
+}
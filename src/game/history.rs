@@ -0,0 +1,339 @@
+use crate::game::card_action::CardAction;
+use crate::game::game_event::GameEvent;
+use crate::messenger::Messenger;
+use crate::state::{Card, Deck, Player, PlayerId, Table};
+use crate::{Error, Game};
+use fehler::{throw, throws};
+use std::rc::Rc;
+
+/// An action that a player took, and the game accepted.
+#[derive(Clone, Debug)]
+pub enum GameAction {
+    Play(CardAction),
+    /// Cards put back on the bottom of the deck, after a card like the
+    /// Chancellor.
+    ReturnCards(Vec<Box<dyn Card>>),
+}
+
+/// When `Game::undo` is allowed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UndoPolicy {
+    /// Any action may be undone.
+    #[default]
+    Always,
+    /// An action may not be undone once it has shown someone a card that
+    /// was hidden from them, or knocked a player out.
+    UntilHiddenInfoRevealed,
+}
+
+/// How a game started: enough to deal it again exactly.
+#[derive(Clone, Debug)]
+pub(crate) struct Start {
+    pub(crate) names: Vec<String>,
+    pub(crate) first_player: PlayerId,
+    pub(crate) deck: Deck,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct LogEntry {
+    pub(crate) action: GameAction,
+    pub(crate) revealed_hidden_info: bool,
+}
+
+/// Every accepted action since the start of the round. A game can be
+/// rebuilt by dealing the starting deck again and replaying the log.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct History {
+    /// None for a game that didn't start from a deal, like one restored
+    /// from a save that had no history.
+    pub(crate) start: Option<Start>,
+    /// The seed that shuffled the starting deck, if there was one.
+    pub(crate) seed: Option<u64>,
+    pub(crate) log: Vec<LogEntry>,
+    /// Undone actions, most recently undone last.
+    pub(crate) undone: Vec<GameAction>,
+    pub(crate) policy: UndoPolicy,
+}
+
+impl History {
+    #[throws]
+    pub(crate) fn new(table: &Table, first_player: PlayerId, deck: &Deck) -> History {
+        let names = table
+            .player_ids()
+            .map(|id| Ok(table.player(id)?.name().to_string()))
+            .collect::<Result<Vec<_>, Error>>()?;
+        History {
+            start: Some(Start {
+                names,
+                first_player,
+                deck: deck.clone(),
+            }),
+            ..History::default()
+        }
+    }
+
+    pub(crate) fn start(&self) -> Option<&Start> {
+        self.start.as_ref()
+    }
+
+    pub(crate) fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+
+    pub(crate) fn clear_undone(&mut self) {
+        self.undone.clear();
+    }
+}
+
+/// Passes messages on, noting whether any of the events revealed hidden
+/// information.
+struct RevealWatcher<'a, M: Messenger + ?Sized> {
+    messenger: &'a mut M,
+    revealed_hidden_info: bool,
+}
+
+impl<'a, M: Messenger + ?Sized> Messenger for RevealWatcher<'a, M> {
+    fn message(&mut self, player: Option<&Player>, msg: &str) {
+        self.messenger.message(player, msg);
+    }
+
+    fn event(&mut self, game: &Game, event: &GameEvent) {
+        self.revealed_hidden_info |= event.reveals_hidden_info();
+        self.messenger.event(game, event);
+    }
+}
+
+/// Discards everything, for replaying actions that were already reported.
+pub(crate) struct Silent;
+
+impl Messenger for Silent {
+    fn message(&mut self, _player: Option<&Player>, _msg: &str) {}
+
+    fn event(&mut self, _game: &Game, _event: &GameEvent) {}
+}
+
+impl Game {
+    /// The actions accepted so far this round, in the order they were taken.
+    pub fn actions(&self) -> impl Iterator<Item = &GameAction> {
+        self.history.log.iter().map(|entry| &entry.action)
+    }
+
+    /// The seed that shuffled the deck, if the game was dealt from one.
+    /// Games built by a GameBuilder or a Match always have a seed. It is
+    /// drawn from the builder's generator, so it isn't the number passed
+    /// to `GameBuilder::seed`.
+    pub fn seed(&self) -> Option<u64> {
+        self.history.seed
+    }
+
+    pub fn undo_policy(&self) -> UndoPolicy {
+        self.history.policy
+    }
+
+    pub fn set_undo_policy(&mut self, policy: UndoPolicy) {
+        self.history.policy = policy;
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.check_undo().is_ok()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.history.undone.is_empty()
+    }
+
+    /// Takes back the most recent action. The game is dealt again from the
+    /// start and every earlier action is replayed, without any messages.
+    #[throws]
+    pub fn undo(&mut self) {
+        self.check_undo()?;
+        let mut history = self.history.clone();
+        let undone = history.log.pop().map(|entry| entry.action);
+
+        let mut game = self.rebuild(&history)?;
+        game.history.undone = history.undone;
+        game.history.undone.extend(undone);
+        game.history.policy = history.policy;
+        game.history.seed = history.seed;
+        *self = game;
+    }
+
+    /// Takes the most recently undone action again.
+    #[throws]
+    pub fn redo(&mut self, messenger: &mut impl Messenger) {
+        let action = if let Some(action) = self.history.undone.last() {
+            action.clone()
+        } else {
+            throw!(Error::NothingToRedo);
+        };
+        self.take_action(action, messenger)?;
+        self.history.undone.pop();
+    }
+
+    #[throws]
+    fn check_undo(&self) {
+        let entry = if let Some(entry) = self.history.log.last() {
+            entry
+        } else {
+            throw!(Error::NothingToUndo);
+        };
+        if self.history.policy == UndoPolicy::UntilHiddenInfoRevealed && entry.revealed_hidden_info
+        {
+            throw!(Error::UndoAfterHiddenInfoRevealed);
+        }
+    }
+
+    /// Deals the game again and replays every action in `history`.
+    #[throws]
+    fn rebuild(&self, history: &History) -> Game {
+        let start = if let Some(start) = &history.start {
+            start
+        } else {
+            throw!(Error::NothingToUndo);
+        };
+        let mut game = Game::with_table(
+            Rc::clone(&self.ruleset),
+            Table::with_names(&start.names)?,
+            start.first_player,
+            start.deck.clone(),
+        )?;
+        #[cfg(test)]
+        {
+            game.stacked_deck = self.stacked_deck.clone();
+        }
+        for entry in &history.log {
+            game.take_action(entry.action.clone(), &mut Silent)?;
+        }
+        game
+    }
+
+    /// Performs the action and adds it to the log.
+    #[throws]
+    pub(crate) fn take_action(&mut self, action: GameAction, messenger: &mut impl Messenger) {
+        let mut watcher = RevealWatcher {
+            messenger,
+            revealed_hidden_info: false,
+        };
+        match &action {
+            GameAction::Play(card_action) => self.play_card(card_action.clone(), &mut watcher)?,
+            GameAction::ReturnCards(cards) => {
+                let cards = cards.iter().map(|c| c.as_ref()).collect::<Vec<_>>();
+                self.put_cards_back(&cards, &mut watcher)?
+            }
+        }
+        self.history.log.push(LogEntry {
+            action,
+            revealed_hidden_info: watcher.revealed_hidden_info,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::test::{deck, hand_of, original};
+    use crate::game::RevisedRuleset;
+    use crate::messenger::test::TestMessenger;
+    use crate::state::{OriginalCard::*, RevisedCard};
+
+    #[test]
+    #[throws]
+    fn test_undo_redo() {
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Handmaid, Guard, Baron, Priest, Countess, King, Prince]),
+        )?;
+        let mut messenger = TestMessenger::new();
+        assert_eq!(Some(Error::NothingToUndo), game.undo().err());
+
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        game.perform_action(
+            CardAction::new(King, PlayerId(1), Some(PlayerId(2)), None),
+            &mut messenger,
+        )?;
+        let after_king = game.to_string();
+        assert_eq!(2, game.actions().count());
+
+        game.undo()?;
+        assert_eq!(PlayerId(1), game.current_player());
+        assert_eq!(vec![Guard, King], hand_of(&game, 1));
+        assert_eq!(1, game.actions().count());
+        assert!(game.can_redo());
+
+        game.undo()?;
+        assert_eq!(PlayerId(0), game.current_player());
+        assert!(!game.is_protected(PlayerId(0)));
+        assert!(!game.can_undo());
+
+        game.redo(&mut messenger)?;
+        game.redo(&mut messenger)?;
+        assert_eq!(after_king, game.to_string());
+        assert_eq!(Some(Error::NothingToRedo), game.redo(&mut messenger).err());
+
+        // A new action can't be followed by a redo.
+        game.undo()?;
+        game.perform_action(
+            CardAction::new(Guard, PlayerId(1), Some(PlayerId(2)), Some(Priest)),
+            &mut messenger,
+        )?;
+        assert!(!game.can_redo());
+    }
+
+    #[test]
+    #[throws]
+    fn test_undo_until_hidden_info_revealed() {
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Handmaid, Guard, Baron, Priest, Countess, King, Prince]),
+        )?;
+        game.set_undo_policy(UndoPolicy::UntilHiddenInfoRevealed);
+        let mut messenger = TestMessenger::new();
+
+        // Passing the turn shows the next player the card they draw.
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        assert!(!game.can_undo());
+        assert_eq!(Some(Error::UndoAfterHiddenInfoRevealed), game.undo().err());
+
+        game.set_undo_policy(UndoPolicy::Always);
+        game.undo()?;
+        assert_eq!(UndoPolicy::Always, game.undo_policy());
+        assert_eq!(PlayerId(0), game.current_player());
+    }
+
+    #[test]
+    #[throws]
+    fn test_undo_returned_cards() {
+        use RevisedCard::*;
+        let mut game = Game::with_deck(
+            Rc::new(RevisedRuleset),
+            2,
+            PlayerId(0),
+            deck(&[
+                Chancellor, Guard, Spy, Priest, Baron, Handmaid, King, Prince, Countess,
+            ]),
+        )?;
+        let mut messenger = TestMessenger::new();
+        game.perform_action(
+            CardAction::new(Chancellor, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        game.return_cards(&[Prince, Countess], &mut messenger)?;
+        let returned = game.to_string();
+
+        game.undo()?;
+        assert_eq!(3, game.player(PlayerId(0))?.hand().len());
+        game.redo(&mut messenger)?;
+        assert_eq!(returned, game.to_string());
+    }
+}
@@ -0,0 +1,210 @@
+use crate::game::card_action::CardAction;
+use crate::game::card_rules::SecondTarget;
+use crate::state::{Card, PlayerId};
+use crate::{Error, Game};
+use fehler::throws;
+
+impl Game {
+    /// Lists every action that the player may take with the cards in their
+    /// hand. The list is empty unless it is the player's turn.
+    ///
+    /// Each candidate is checked by the same rules as `perform_action`, so
+    /// every action listed will be accepted, and no other will. Actions
+    /// that differ only in the edition of their cards count as the same.
+    #[throws]
+    pub fn legal_actions(&self, player_id: PlayerId) -> Vec<CardAction> {
+        let player = self.player(player_id)?;
+        if self.is_round_over() || self.cards_to_return > 0 || player_id != self.current_player {
+            return Vec::new();
+        }
+
+        let players = self.table.player_ids().collect::<Vec<_>>();
+        let mut deck = self.ruleset.deck();
+        let guesses = distinct(&deck.deal(deck.cards_remaining()));
+
+        let mut actions = Vec::new();
+        for card in distinct(player.hand()) {
+            let rules = self.ruleset.rules_for_card(card.as_ref())?;
+
+            // With no target, a card has either no effect or its only one.
+            let mut targets = vec![(None, None)];
+            if rules.target_required() {
+                for first in &players {
+                    targets.push((Some(*first), None));
+                    if *rules.second_target() != SecondTarget::NotAllowed {
+                        for second in &players {
+                            targets.push((Some(*first), Some(*second)));
+                        }
+                    }
+                }
+            }
+
+            for (target, second_target) in targets {
+                let card_guesses = if rules.guess_required() && target.is_some() {
+                    guesses.iter().map(|g| Some(g.clone())).collect()
+                } else {
+                    vec![None]
+                };
+                for guess in card_guesses {
+                    let action = CardAction::from_parts(
+                        card.clone(),
+                        player_id,
+                        target,
+                        second_target,
+                        guess,
+                    );
+                    if self.is_valid_action(&action).is_ok() {
+                        actions.push(action);
+                    }
+                }
+            }
+        }
+        actions
+    }
+}
+
+/// One copy of each different card.
+pub(super) fn distinct(cards: &[Box<dyn Card>]) -> Vec<Box<dyn Card>> {
+    let mut distinct: Vec<Box<dyn Card>> = Vec::new();
+    for card in cards {
+        if !distinct.iter().any(|c| c.is_same_card(card.as_ref())) {
+            distinct.push(card.clone());
+        }
+    }
+    distinct
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::test::{deck, original};
+    use crate::game::{GameBuilder, PremiumRuleset, RevisedRuleset};
+    use crate::messenger::test::TestMessenger;
+    use crate::state::OriginalCard::*;
+    use std::rc::Rc;
+
+    #[test]
+    #[throws]
+    fn test_legal_actions() {
+        let game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Guard, Priest, Baron, Countess, Handmaid]),
+        )?;
+        let actions = game.legal_actions(PlayerId(0))?;
+        // Two targets for the Guard, and any guess but a Guard.
+        assert_eq!(2 * 7 + 1, actions.len());
+        assert!(actions
+            .iter()
+            .all(|a| a.guess().map_or(true, |g| !Guard.is_same_card(g))));
+        assert!(actions
+            .iter()
+            .any(|a| Handmaid.is_same_card(a.card()) && !a.has_target()));
+
+        assert!(game.legal_actions(PlayerId(1))?.is_empty());
+        assert_eq!(
+            Some(Error::InvalidPlayerNumber(PlayerId(3))),
+            game.legal_actions(PlayerId(3)).err()
+        );
+    }
+
+    #[test]
+    #[throws]
+    fn test_countess_and_protection() {
+        let mut game = Game::with_deck(
+            original(),
+            2,
+            PlayerId(0),
+            deck(&[
+                King, Handmaid, Guard, Guard, Guard, Guard, Countess, Prince, Baron,
+            ]),
+        )?;
+        let actions = game.legal_actions(PlayerId(0))?;
+        assert_eq!(1, actions.len());
+        assert!(Countess.is_same_card(actions[0].card()));
+
+        game.perform_action(actions[0].clone(), &mut TestMessenger::new())?;
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(1), None, None),
+            &mut TestMessenger::new(),
+        )?;
+        // Player 2 is protected, so the King and Baron may only be played with no target.
+        let actions = game.legal_actions(PlayerId(0))?;
+        assert_eq!(2, actions.len());
+        assert!(actions.iter().all(|a| !a.has_target()));
+    }
+
+    #[test]
+    #[throws]
+    fn test_every_legal_action_is_accepted() {
+        for seed in 0..4 {
+            let mut game = GameBuilder::new()
+                .num_players(5)
+                .ruleset(PremiumRuleset)
+                .seed(seed)
+                .build()?;
+            let mut messenger = TestMessenger::new();
+            while !game.is_round_over() {
+                let actions = game.legal_actions(game.current_player())?;
+                assert!(!actions.is_empty());
+                for action in &actions {
+                    game.perform_action(action.clone(), &mut messenger)?;
+                    game.undo()?;
+                }
+                game.perform_action(actions[0].clone(), &mut messenger)?;
+            }
+        }
+    }
+
+    #[test]
+    #[throws]
+    fn test_every_accepted_action_is_legal() {
+        // Guesses from every edition, and targets that aren't at the table.
+        let mut cards = Vec::new();
+        for ruleset in &[original(), Rc::new(PremiumRuleset), Rc::new(RevisedRuleset)] {
+            let mut deck = ruleset.deck();
+            cards.extend(deck.deal(deck.cards_remaining()));
+        }
+        let guesses = distinct(&cards);
+        let players = (0..6).map(|index| Some(PlayerId(index)));
+        let targets = std::iter::once(None).chain(players).collect::<Vec<_>>();
+
+        for seed in 0..2 {
+            let mut game = GameBuilder::new()
+                .num_players(5)
+                .ruleset(PremiumRuleset)
+                .seed(seed)
+                .build()?;
+            while !game.is_round_over() {
+                let current = game.current_player();
+                let legal = game
+                    .legal_actions(current)?
+                    .iter()
+                    .map(CardAction::to_command)
+                    .collect::<Vec<_>>();
+                for card in distinct(game.player(current)?.hand()) {
+                    for target in &targets {
+                        for second_target in &targets {
+                            let card_guesses = guesses.iter().cloned().map(Some);
+                            for guess in std::iter::once(None).chain(card_guesses) {
+                                let action = CardAction::from_parts(
+                                    card.clone(),
+                                    current,
+                                    *target,
+                                    *second_target,
+                                    guess,
+                                );
+                                if game.is_valid_action(&action).is_ok() {
+                                    assert!(legal.contains(&action.to_command()));
+                                }
+                            }
+                        }
+                    }
+                }
+                let action = game.legal_actions(current)?.remove(0);
+                game.perform_action(action, &mut TestMessenger::new())?;
+            }
+        }
+    }
+}
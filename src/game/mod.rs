@@ -1,31 +1,167 @@
-mod original;
 mod card_action;
 mod card_rules;
+mod command;
+mod game_event;
+mod game_match;
+mod history;
+mod legal_actions;
+mod original;
+mod player_view;
+mod premium;
+mod replay;
+mod revised;
+mod round_outcome;
+mod ruleset;
+#[cfg(feature = "serde")]
+mod saved_game;
+mod seating;
+mod validation;
 
 pub use crate::game::card_action::CardAction;
+pub use crate::game::card_rules::{CardRules, HandConstraint, SecondTarget};
+pub use crate::game::game_event::{Audience, EliminationReason, GameEvent};
+pub use crate::game::game_match::Match;
+use crate::game::history::History;
+pub use crate::game::history::{GameAction, UndoPolicy};
+pub use crate::game::original::OriginalRuleset;
+pub use crate::game::player_view::{PlayerSummary, PlayerView, RevealedCard};
+pub use crate::game::premium::PremiumRuleset;
+pub use crate::game::replay::Replay;
+pub use crate::game::revised::RevisedRuleset;
+pub use crate::game::round_outcome::{RoundEndReason, RoundOutcome};
+pub use crate::game::ruleset::{builtin_ruleset, Ruleset};
+#[cfg(feature = "serde")]
+pub use crate::game::saved_game::{SavedAction, SavedGame, SavedReplay};
+pub use crate::game::seating::{FirstPlayer, PlayerProfile};
+pub use crate::game::validation::{ActionPart, ValidationReport, Violation};
 use crate::messenger::Messenger;
-use crate::state::{Player, Table};
+use crate::state::seeded_rng;
+use crate::state::{Card, CardId, CardLocation, Deck, Named, Player, PlayerId, Table};
 use crate::Error;
 use fehler::{throw, throws};
+use rand::{thread_rng, RngCore};
+use std::borrow::Cow;
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
+use std::rc::Rc;
 
 pub struct GameBuilder {
     num_players: usize,
+    players: Option<Vec<PlayerProfile>>,
+    seating: Option<Vec<usize>>,
+    first_player: FirstPlayer,
+    ruleset: Rc<dyn Ruleset>,
+    rng: Option<Box<dyn RngCore>>,
 }
 
 impl GameBuilder {
     pub fn new() -> GameBuilder {
-        GameBuilder { num_players: 4 }
+        GameBuilder {
+            num_players: 4,
+            players: None,
+            seating: None,
+            first_player: FirstPlayer::default(),
+            ruleset: Rc::new(OriginalRuleset),
+            rng: None,
+        }
     }
 
+    /// Builds a Game that plays a single round.
     #[throws]
     pub fn build(self) -> Game {
-        Game::new(self.num_players)?
+        let mut rng = self.rng.unwrap_or_else(|| Box::new(thread_rng()));
+        let players = seated_players(
+            self.ruleset.as_ref(),
+            self.num_players,
+            self.players,
+            self.seating,
+        )?;
+        let first_player = self.first_player.choose(&players, &mut rng)?;
+        // The shuffle has a seed of its own, drawn after the first player
+        // is chosen, so that the game can be dealt again from `Game::seed`.
+        let seed = rng.next_u64();
+        Game::from_seed(self.ruleset, &player_names(&players), first_player, seed)?
     }
 
+    /// Builds a Match that plays rounds until one player has collected
+    /// enough tokens of affection.
+    #[throws]
+    pub fn build_match(self) -> Match {
+        let mut rng = self.rng.unwrap_or_else(|| Box::new(thread_rng()));
+        let players = seated_players(
+            self.ruleset.as_ref(),
+            self.num_players,
+            self.players,
+            self.seating,
+        )?;
+        let first_player = self.first_player.choose(&players, &mut rng)?;
+        Match::new(self.ruleset, player_names(&players), first_player, rng)?
+    }
+
+    /// Plays with `num_players` players named "Player 1", "Player 2", and
+    /// so on. This replaces any players added with `player_names` or
+    /// `players`.
     pub fn num_players(mut self, num_players: usize) -> GameBuilder {
         self.num_players = num_players;
+        self.players = None;
+        self
+    }
+
+    /// Plays with one player for each name. Names must be unique.
+    pub fn player_names<S: AsRef<str>>(self, names: &[S]) -> GameBuilder {
+        self.players(
+            names
+                .iter()
+                .map(|name| PlayerProfile::new(name.as_ref()))
+                .collect(),
+        )
+    }
+
+    /// Plays with the given players. Names must be unique.
+    pub fn players(mut self, players: Vec<PlayerProfile>) -> GameBuilder {
+        self.num_players = players.len();
+        self.players = Some(players);
+        self
+    }
+
+    /// Seats the players in a different order than they were added. Seat
+    /// `n` is given to player `seating[n]`, so `seating` must list each
+    /// player exactly once.
+    pub fn seating(mut self, seating: &[usize]) -> GameBuilder {
+        self.seating = Some(seating.to_vec());
+        self
+    }
+
+    /// Chooses who goes first. The default is whoever sits in the first
+    /// seat. Seats are counted after applying `seating`.
+    pub fn first_player(mut self, first_player: FirstPlayer) -> GameBuilder {
+        self.first_player = first_player;
+        self
+    }
+
+    /// Sets the edition, or variant, to play. The default is the
+    /// original edition.
+    pub fn ruleset(mut self, ruleset: impl Ruleset + 'static) -> GameBuilder {
+        self.ruleset = Rc::new(ruleset);
+        self
+    }
+
+    /// Uses a generator created from `seed` in place of `rng`. Games built
+    /// with the same seed, and played the same way, are identical on every
+    /// machine.
+    pub fn seed(self, seed: u64) -> GameBuilder {
+        self.rng(seeded_rng(seed))
+    }
+
+    /// Chooses a random first player, and seeds each round's shuffle, with
+    /// the supplied random number generator. The default is
+    /// `rand::thread_rng()`.
+    ///
+    /// The deck isn't shuffled by `rng` itself. Each round's deck is
+    /// shuffled by a generator seeded with the next number from `rng`, so
+    /// that the round can be replayed from `Game::seed`.
+    pub fn rng(mut self, rng: impl RngCore + 'static) -> GameBuilder {
+        self.rng = Some(Box::new(rng));
         self
     }
 }
@@ -36,106 +172,589 @@ impl Default for GameBuilder {
     }
 }
 
+/// The players, in their seats, with default names if none were given.
+#[throws]
+fn seated_players(
+    ruleset: &dyn Ruleset,
+    num_players: usize,
+    players: Option<Vec<PlayerProfile>>,
+    seating: Option<Vec<usize>>,
+) -> Vec<PlayerProfile> {
+    if !ruleset.player_counts().contains(&num_players) {
+        throw!(Error::InvalidNumberOfPlayers(num_players));
+    }
+    let players = players.unwrap_or_else(|| {
+        (0..num_players)
+            .map(|index| PlayerProfile::new(&PlayerId(index).to_string()))
+            .collect()
+    });
+    if let Some(seating) = seating {
+        seating::seat_players(players, &seating)?
+    } else {
+        players
+    }
+}
+
+fn player_names(players: &[PlayerProfile]) -> Vec<String> {
+    players.iter().map(|p| p.name().to_string()).collect()
+}
+
 #[derive(Debug)]
 pub struct Game {
+    ruleset: Rc<dyn Ruleset>,
     table: Table,
+    /// For tests, the stacked deck that the round was dealt from. It is
+    /// audited in place of the ruleset's deck.
+    #[cfg(test)]
+    stacked_deck: Option<Deck>,
+
+    current_player: PlayerId,
+    active: HashSet<PlayerId>,
+    protected: HashSet<PlayerId>,
+
+    /// A player that the next card played must target, if it can.
+    forced_target: Option<PlayerId>,
+    /// (player, chosen) pairs. If the chosen player wins the round, the
+    /// player receives a token.
+    jesters: Vec<(PlayerId, PlayerId)>,
+    /// One entry for each token of affection earned, other than by
+    /// winning the round.
+    bonus_tokens: Vec<PlayerId>,
+    /// The number of cards that the current player must put on the bottom
+    /// of the deck before their turn ends.
+    cards_to_return: usize,
+    /// (viewer, owner, card) for each card that a player has been shown from
+    /// another player's hand, in the order they were shown.
+    revealed: Vec<(PlayerId, PlayerId, Box<dyn Card>)>,
 
-    current_player: usize,
-    active: HashSet<usize>,
-    protected: HashSet<usize>,
+    outcome: Option<RoundOutcome>,
+
+    history: History,
 }
 
 impl Game {
+    /// Deals a game with a deck shuffled by a generator created from `seed`.
+    /// The same seed always deals the same game.
+    #[throws]
+    pub(crate) fn from_seed(
+        ruleset: Rc<dyn Ruleset>,
+        names: &[String],
+        first_player: PlayerId,
+        seed: u64,
+    ) -> Game {
+        let mut deck = ruleset.deck();
+        deck.shuffle_with(&mut seeded_rng(seed));
+        let mut game = Game::with_table(ruleset, Table::with_names(names)?, first_player, deck)?;
+        game.history.set_seed(seed);
+        game
+    }
+
+    /// Creates a game with a stacked deck, for testing.
+    #[cfg(test)]
     #[throws]
-    fn new(num_players: usize) -> Game {
+    fn with_deck(
+        ruleset: Rc<dyn Ruleset>,
+        num_players: usize,
+        first_player: PlayerId,
+        deck: Deck,
+    ) -> Game {
+        Game::with_stacked_deck(ruleset, Table::new(num_players)?, first_player, deck)?
+    }
+
+    /// Creates a game at `table` with a stacked deck, for testing. The deck
+    /// need not hold every card in the ruleset's deck.
+    #[cfg(test)]
+    #[throws]
+    fn with_stacked_deck(
+        ruleset: Rc<dyn Ruleset>,
+        table: Table,
+        first_player: PlayerId,
+        deck: Deck,
+    ) -> Game {
+        let mut game = Game::with_table(ruleset, table, first_player, deck.clone())?;
+        game.stacked_deck = Some(deck);
+        game
+    }
+
+    #[throws]
+    fn with_table(
+        ruleset: Rc<dyn Ruleset>,
+        table: Table,
+        first_player: PlayerId,
+        deck: Deck,
+    ) -> Game {
+        let num_players = table.num_players();
+        if !ruleset.player_counts().contains(&num_players) {
+            throw!(Error::InvalidNumberOfPlayers(num_players));
+        }
         let mut game = Game {
-            table: Table::new(num_players)?,
-            current_player: 0,
+            ruleset,
+            table,
+            #[cfg(test)]
+            stacked_deck: None,
+            current_player: first_player,
             active: HashSet::default(),
             protected: HashSet::default(),
+            forced_target: None,
+            jesters: Vec::new(),
+            bonus_tokens: Vec::new(),
+            cards_to_return: 0,
+            revealed: Vec::new(),
+            outcome: None,
+            history: History::default(),
         };
+        game.history = History::new(&game.table, first_player, &deck)?;
+        game.table.set_deck(deck);
+
+        game.table.player(first_player)?;
+        for player_id in (0..num_players).map(PlayerId) {
+            game.active.insert(player_id);
+            game.deal_one_to_player(player_id)?;
+        }
+
+        if let Some(out_card) = game.table.deck_mut().deal_one() {
+            game.table.set_out_card(Some(out_card));
+        } else {
+            throw!(Error::InternalErrorUnexpectedEmptyDeck);
+        }
 
-        for player_num in 0..num_players {
-            game.active.insert(player_num);
-            game.deal_one_to_player(player_num)?;
+        // In a two-player game, more cards are removed face up.
+        let burn_count = game.ruleset.burned_card_count(num_players);
+        if let Some(burned) = game.table.deck_mut().burn(burn_count) {
+            for card in burned {
+                game.table.burn_card(card);
+            }
+        } else {
+            throw!(Error::InternalErrorUnexpectedEmptyDeck);
         }
 
-	if let Some(out_card) = game.table.deck_mut().deal_one() {
-	    game.table.set_out_card(Some(out_card));
-	} else {
-	    throw!(Error::InternalErrorUnexpectedEmptyDeck);
-	}
+        // The first player draws at the start of their turn, just like everyone else.
+        game.deal_one_to_player(game.current_player)?;
 
         game
     }
 
+    pub fn ruleset(&self) -> &dyn Ruleset {
+        self.ruleset.as_ref()
+    }
+
+    /// The cards removed face up at the start of a two-player game.
+    pub fn burned_cards(&self) -> &[Box<dyn Card>] {
+        self.table.burned_cards()
+    }
+
+    /// Where the physical card with the given id is. This includes hidden
+    /// places, like the deck and the other players' hands.
+    pub fn find_card(&self, id: CardId) -> Option<CardLocation> {
+        self.table.find_card(id).map(|(location, _)| location)
+    }
+
+    /// Every seat at the table, in order, including eliminated players.
+    pub fn player_ids(&self) -> impl Iterator<Item = PlayerId> {
+        self.table.player_ids()
+    }
+
+    /// The name of the player in seat `player_id`.
+    pub fn name_of(&self, player_id: PlayerId) -> Cow<'_, str> {
+        self.table.name_of(player_id)
+    }
+
+    /// Displays the player by name, where `PlayerId` would display their
+    /// seat number.
+    pub fn display(&self, player_id: PlayerId) -> Named<'_> {
+        self.table.display(player_id)
+    }
+
+    /// The error's message, naming players the way this game does.
+    pub fn describe_error(&self, error: &Error) -> String {
+        error.describe(|id| self.name_of(id).into_owned())
+    }
+
+    /// The player whose turn it is.
+    pub fn current_player(&self) -> PlayerId {
+        self.current_player
+    }
+
+    /// Returns true if the player has not been eliminated from the round.
+    pub fn is_active(&self, player_id: PlayerId) -> bool {
+        self.active.contains(&player_id)
+    }
+
+    /// Returns true if the player is protected by a Handmaid.
+    pub fn is_protected(&self, player_id: PlayerId) -> bool {
+        self.protected.contains(&player_id)
+    }
+
+    /// The player that the next card played must target, if any.
+    pub fn forced_target(&self) -> Option<PlayerId> {
+        self.forced_target
+    }
+
+    /// The players who have earned a token of affection this round other
+    /// than by winning it. A player appears once for each token.
+    pub fn bonus_tokens(&self) -> &[PlayerId] {
+        &self.bonus_tokens
+    }
+
+    /// (player, chosen) for each player who chose another player with a
+    /// card like the Jester.
+    pub fn jesters(&self) -> &[(PlayerId, PlayerId)] {
+        &self.jesters
+    }
+
+    /// The number of cards that the current player must return to the
+    /// bottom of the deck, with `return_cards`, before their turn ends.
+    pub fn cards_to_return(&self) -> usize {
+        self.cards_to_return
+    }
+
+    /// The result of the round, or None if the round is still being played.
+    pub fn round_outcome(&self) -> Option<&RoundOutcome> {
+        self.outcome.as_ref()
+    }
+
+    pub fn is_round_over(&self) -> bool {
+        self.outcome.is_some()
+    }
+
     #[throws]
     pub fn perform_action(&mut self, action: CardAction, messenger: &mut impl Messenger) {
+        self.take_action(GameAction::Play(action), messenger)?;
+        // A new action replaces anything that was undone.
+        self.history.clear_undone();
+    }
+
+    #[throws]
+    fn play_card(&mut self, action: CardAction, messenger: &mut impl Messenger) {
+        if self.is_round_over() {
+            throw!(Error::BadActionRoundOver);
+        }
+        if self.cards_to_return > 0 {
+            throw!(Error::BadActionMustReturnCards(self.cards_to_return));
+        }
         self.is_valid_action(&action)?;
 
-        messenger.message(
-            None,
-            &format!("Player {} discards a {}", action.current(), action.card()),
+        messenger.event(
+            self,
+            &GameEvent::CardDiscarded {
+                player: action.current(),
+                card: action.boxed_card(),
+            },
         );
         let current = self.player_mut(self.current_player)?;
-        current.discard(action.card())?;
+        if let Some(index) = current.card_index(action.card()) {
+            current.discard(index)?;
+        }
+
+        // A forced target only applies to the next card played.
+        self.forced_target = None;
 
-        original::perform_card_action(&action, self, messenger)?;
+        let ruleset = Rc::clone(&self.ruleset);
+        ruleset.perform_card_action(&action, self, messenger)?;
 
-        self.make_next_player_current();
+        // The turn isn't over until the player has returned their cards.
+        if self.cards_to_return == 0 {
+            self.end_turn(messenger)?;
+        }
+        self.debug_audit();
     }
 
+    /// Puts cards from the current player's hand on the bottom of the deck,
+    /// in the order given, and ends their turn. This completes a card, like
+    /// the Chancellor, that draws extra cards.
     #[throws]
-    fn make_inactive(&mut self, player_index: usize) {
-        todo!("");
+    pub fn return_cards<C: Card + Clone + 'static>(
+        &mut self,
+        cards: &[C],
+        messenger: &mut impl Messenger,
+    ) {
+        let cards = cards
+            .iter()
+            .map(|c| Box::new(c.clone()) as Box<dyn Card>)
+            .collect();
+        self.take_action(GameAction::ReturnCards(cards), messenger)?;
+        self.history.clear_undone();
     }
 
     #[throws]
-    fn make_protected(&mut self, player_index: usize) {
-        todo!("");
+    fn put_cards_back(&mut self, cards: &[&dyn Card], messenger: &mut impl Messenger) {
+        if self.is_round_over() {
+            throw!(Error::BadActionRoundOver);
+        }
+        if self.cards_to_return == 0 {
+            throw!(Error::BadActionNoCardsToReturn);
+        }
+        if cards.len() != self.cards_to_return {
+            throw!(Error::BadActionMustReturnCards(self.cards_to_return));
+        }
+
+        // Check the whole hand before moving anything, so that a bad request
+        // leaves the hand untouched.
+        let current = self.current_player;
+        let hand = self.player(current)?.hand();
+        let mut used = Vec::new();
+        for card in cards.iter().copied() {
+            let index = hand
+                .iter()
+                .enumerate()
+                .find(|(index, c)| !used.contains(index) && c.is_same_card(card))
+                .map(|(index, _)| index);
+            if let Some(index) = index {
+                used.push(index);
+            } else {
+                throw!(Error::BadActionPlayerDoesntHaveCard(
+                    current,
+                    card.name().to_string()
+                ));
+            }
+        }
+
+        for card in cards.iter().copied() {
+            let player = self.player_mut(current)?;
+            if let Some(index) = player.card_index(card) {
+                let card = player.take_from_hand(index)?;
+                self.table.deck_mut().put_on_bottom(card);
+            }
+        }
+        messenger.event(
+            self,
+            &GameEvent::CardsReturned {
+                player: current,
+                count: cards.len(),
+            },
+        );
+
+        self.cards_to_return = 0;
+        self.end_turn(messenger)?;
+        self.debug_audit();
     }
 
+    /// Checks for states that the rules should never allow: a card that is
+    /// lost, copied, in two places, or not in the ruleset's deck, a hand of
+    /// the wrong size for the turn, or an eliminated player who still holds
+    /// cards.
     #[throws]
-    fn make_unprotected(&mut self, player_index: usize) {
-	todo!("");
+    pub fn audit(&self) {
+        self.table.audit(&self.composition())?;
+
+        for player_id in self.table.player_ids() {
+            let hand_size = self.player(player_id)?.hand().len();
+            let allowed = if !self.is_active(player_id) {
+                0..=0
+            } else if self.is_round_over() || player_id != self.current_player {
+                1..=1
+            } else if self.cards_to_return > 0 {
+                // The player has played a card and drawn cards to return.
+                1 + self.cards_to_return..=1 + self.cards_to_return
+            } else if self.is_deck_empty() {
+                // There may have been no card to draw.
+                1..=2
+            } else {
+                2..=2
+            };
+            if !allowed.contains(&hand_size) {
+                throw!(Error::InternalErrorInvariantViolated(format!(
+                    "{} holds {} cards",
+                    self.display(player_id),
+                    hand_size
+                )));
+            }
+        }
     }
 
-    fn make_next_player_current(&mut self) {
-        todo!("");
+    /// Every card that should be in the round.
+    fn composition(&self) -> Deck {
+        #[cfg(test)]
+        if let Some(deck) = &self.stacked_deck {
+            return deck.clone();
+        }
+        self.ruleset.deck()
     }
 
-    fn is_deck_empty(&self) -> bool {
+    /// Panics if the game fails its audit, so that bugs in the rules are
+    /// found quickly. Only debug builds are audited.
+    fn debug_audit(&self) {
+        if cfg!(debug_assertions) {
+            if let Err(error) = self.audit() {
+                panic!("{}", error);
+            }
+        }
+    }
+
+    /// Ends the round if it is over. Otherwise, passes the turn on.
+    #[throws]
+    fn end_turn(&mut self, messenger: &mut impl Messenger) {
+        let ruleset = Rc::clone(&self.ruleset);
+        if let Some(outcome) = ruleset.round_outcome(self)? {
+            messenger.event(
+                self,
+                &GameEvent::RoundWon {
+                    outcome: outcome.clone(),
+                },
+            );
+            let bonus_tokens = ruleset.round_end_bonus_tokens(self, &outcome)?;
+            self.bonus_tokens.extend(bonus_tokens);
+            self.outcome = Some(outcome);
+        } else {
+            self.make_next_player_current(messenger)?;
+        }
+    }
+
+    /// Eliminates the player from the round. Their hand is discarded face up.
+    ///
+    /// This, and the methods below it, are the changes that a Ruleset may
+    /// make to the game while applying a card's effect. The Ruleset is
+    /// responsible for sending a GameEvent for each change.
+    #[throws]
+    pub fn make_inactive(&mut self, player_id: PlayerId) {
+        let ruleset = Rc::clone(&self.ruleset);
+        ruleset.player_eliminated(self, player_id)?;
+
+        self.player_mut(player_id)?.discard_hand();
+        self.active.remove(&player_id);
+        self.protected.remove(&player_id);
+    }
+
+    /// Makes the next card played target `player_id`, if it can.
+    #[throws]
+    pub fn set_forced_target(&mut self, player_id: PlayerId) {
+        self.player(player_id)?;
+        self.forced_target = Some(player_id);
+    }
+
+    /// Records that `player_id` chose `chosen` with a card like the Jester.
+    /// The players are available to `Ruleset::round_end_bonus_tokens`
+    /// through `jesters`.
+    #[throws]
+    pub fn add_jester(&mut self, player_id: PlayerId, chosen: PlayerId) {
+        self.player(player_id)?;
+        self.player(chosen)?;
+        self.jesters.push((player_id, chosen));
+    }
+
+    /// Deals up to `count` extra cards to the current player, who must then
+    /// return the same number of cards to the bottom of the deck.
+    #[throws]
+    pub fn draw_cards_to_return(&mut self, count: usize) {
+        let cards = self.table.deck_mut().deal(count);
+        self.cards_to_return += cards.len();
+        let player = self.player_mut(self.current_player)?;
+        for card in cards {
+            player.add_card_to_hand(card);
+        }
+    }
+
+    /// Records that `viewer` has been shown the card in `owner`'s hand.
+    #[throws]
+    pub fn reveal_hand(&mut self, viewer: PlayerId, owner: PlayerId) {
+        self.player(viewer)?;
+        let card = self.hand_card(owner)?;
+        self.revealed.push((viewer, owner, card));
+    }
+
+    /// A copy of the only card in the player's hand.
+    #[throws]
+    pub fn hand_card(&self, player_id: PlayerId) -> Box<dyn Card> {
+        let player = self.player(player_id)?;
+        player.card_in_hand()?;
+        player.hand()[0].clone()
+    }
+
+    /// Gives the player a token of affection that doesn't depend on who
+    /// wins the round.
+    #[throws]
+    pub fn award_bonus_token(&mut self, player_id: PlayerId) {
+        self.player(player_id)?;
+        self.bonus_tokens.push(player_id);
+    }
+
+    /// Protects the player, like the Handmaid, until their next turn.
+    #[throws]
+    pub fn make_protected(&mut self, player_id: PlayerId) {
+        self.player(player_id)?;
+        self.protected.insert(player_id);
+    }
+
+    #[throws]
+    pub fn make_unprotected(&mut self, player_id: PlayerId) {
+        self.player(player_id)?;
+        self.protected.remove(&player_id);
+    }
+
+    /// Passes the turn to the next active player, skipping eliminated players.
+    /// The new current player loses any Handmaid protection and draws a card.
+    #[throws]
+    fn make_next_player_current(&mut self, messenger: &mut impl Messenger) {
+        let num_players = self.table.num_players();
+        let next = (1..=num_players)
+            .map(|offset| PlayerId((self.current_player.index() + offset) % num_players))
+            .find(|index| self.active.contains(index));
+
+        if let Some(next) = next {
+            self.current_player = next;
+            self.make_unprotected(next)?;
+            if let Some(card) = self.table.deck_mut().deal_one() {
+                self.player_mut(next)?.add_card_to_hand(card.clone());
+                messenger.event(self, &GameEvent::CardDealt { player: next, card });
+            }
+        }
+    }
+
+    /// Swaps the hands of two players.
+    #[throws]
+    pub fn swap_hands(&mut self, first: PlayerId, second: PlayerId) {
+        self.table.swap_hands(first, second)?;
+    }
+
+    /// Deals a replacement card to a player who was forced to discard.
+    /// If the deck is empty, they receive the card that was set aside at
+    /// the start of the round.
+    #[throws]
+    pub fn redraw(&mut self, player_id: PlayerId) {
+        let card = if let Some(card) = self.table.deck_mut().deal_one() {
+            card
+        } else if let Some(card) = self.table.take_out_card() {
+            card
+        } else {
+            throw!(Error::InternalErrorUnexpectedEmptyDeck);
+        };
+        self.player_mut(player_id)?.add_card_to_hand(card);
+    }
+
+    /// Moves the player's hand to their discard pile, face up, without
+    /// eliminating them. Follow this with `redraw`.
+    #[throws]
+    pub fn discard_hand(&mut self, player_id: PlayerId) {
+        self.player_mut(player_id)?.discard_hand();
+    }
+
+    pub fn is_deck_empty(&self) -> bool {
         self.table.deck().cards_remaining() == 0
     }
 
     #[throws]
-    fn deal_one_to_player(&mut self, player_num: usize) {
+    fn deal_one_to_player(&mut self, player_id: PlayerId) {
         if let Some(card) = self.table.deck_mut().deal_one() {
-            let player = self.table.player_mut(player_num)?;
+            let player = self.table.player_mut(player_id)?;
             player.add_card_to_hand(card);
         }
     }
 
     #[throws]
-    fn player(&self, player_num: usize) -> &Player {
-        self.table.player(player_num)?
+    pub fn player(&self, player_id: PlayerId) -> &Player {
+        self.table.player(player_id)?
     }
 
     #[throws]
-    fn player_mut(&mut self, player_num: usize) -> &mut Player {
-        self.table.player_mut(player_num)?
+    fn player_mut(&mut self, player_id: PlayerId) -> &mut Player {
+        self.table.player_mut(player_id)?
     }
 
     #[throws]
     fn is_valid_action(&self, action: &CardAction) {
-        let player = self.player(action.current())?;
-        let card = action.card();
-        if player.card_index(card).is_none() {
-            throw!(Error::BadActionPlayerDoesntHaveCard(action.current(), card));
+        if let Some(violation) = self.action_violations(action).into_iter().next() {
+            throw!(violation.into_error());
         }
-
-        let rules = original::rules_for_card(card);
-        rules.action_allowed(&action, self.current_player, &self.active, &self.protected)?;
     }
 }
 
@@ -144,3 +763,566 @@ impl Display for Game {
         write!(f, "{}", self.table)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::messenger::test::TestMessenger;
+    use crate::state::OriginalCard;
+
+    pub fn original() -> Rc<dyn Ruleset> {
+        Rc::new(OriginalRuleset)
+    }
+
+    pub fn deck<C: Card + Copy + 'static>(cards: &[C]) -> Deck {
+        Deck::new(
+            cards
+                .iter()
+                .map(|c| Box::new(*c) as Box<dyn Card>)
+                .collect(),
+        )
+    }
+
+    pub fn hand_of(game: &Game, player_index: usize) -> Vec<OriginalCard> {
+        game.player(PlayerId(player_index))
+            .unwrap()
+            .hand()
+            .iter()
+            .map(|c| OriginalCard::from_card(c.as_ref()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    #[throws]
+    fn test_turn_rotation() {
+        use OriginalCard::*;
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[
+                Handmaid, Priest, Guard, Countess, Handmaid, Guard, Handmaid, Baron, King,
+            ]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        assert_eq!(PlayerId(0), game.current_player());
+        assert_eq!(vec![Handmaid, Handmaid], hand_of(&game, 0));
+        assert_eq!(vec![Priest], hand_of(&game, 1));
+
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        assert!(game.is_protected(PlayerId(0)));
+        assert_eq!(PlayerId(1), game.current_player());
+        assert_eq!(vec![Priest, Guard], hand_of(&game, 1));
+
+        game.perform_action(
+            CardAction::new(Priest, PlayerId(1), Some(PlayerId(2)), None),
+            &mut messenger,
+        )?;
+        assert_eq!(PlayerId(2), game.current_player());
+        assert_eq!(vec![Guard, Handmaid], hand_of(&game, 2));
+
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(2), None, None),
+            &mut messenger,
+        )?;
+        assert_eq!(PlayerId(0), game.current_player());
+        assert!(!game.is_protected(PlayerId(0)));
+        assert!(game.is_protected(PlayerId(2)));
+        assert_eq!(vec![Handmaid, Baron], hand_of(&game, 0));
+    }
+
+    #[test]
+    #[throws]
+    fn test_eliminated_player_is_skipped() {
+        use OriginalCard::*;
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Guard, Priest, Baron, Countess, Handmaid, King, Prince]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Guard, PlayerId(0), Some(PlayerId(1)), Some(Priest)),
+            &mut messenger,
+        )?;
+        assert!(!game.is_active(PlayerId(1)));
+        assert!(game.player(PlayerId(1))?.hand().is_empty());
+
+        assert_eq!(PlayerId(2), game.current_player());
+        assert_eq!(vec![Baron, King], hand_of(&game, 2));
+
+        assert_eq!(
+            Some(Error::BadActionTargetingInactive(PlayerId(1))),
+            game.perform_action(
+                CardAction::new(King, PlayerId(2), Some(PlayerId(1)), None),
+                &mut messenger
+            )
+            .err()
+        );
+    }
+
+    #[test]
+    #[throws]
+    fn test_last_player_standing() {
+        use OriginalCard::*;
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Guard, Priest, Guard, Countess, Baron, Handmaid, King]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Guard, PlayerId(0), Some(PlayerId(1)), Some(Priest)),
+            &mut messenger,
+        )?;
+        assert!(!game.is_round_over());
+        game.perform_action(
+            CardAction::new(Guard, PlayerId(2), Some(PlayerId(0)), Some(Baron)),
+            &mut messenger,
+        )?;
+
+        assert_eq!(
+            Some(&RoundOutcome::new(
+                vec![PlayerId(2)],
+                RoundEndReason::LastPlayerStanding
+            )),
+            game.round_outcome()
+        );
+        assert_eq!(
+            Some(Error::BadActionRoundOver),
+            game.perform_action(
+                CardAction::new(Handmaid, PlayerId(2), None, None),
+                &mut messenger
+            )
+            .err()
+        );
+    }
+
+    #[test]
+    #[throws]
+    fn test_deck_empty() {
+        use OriginalCard::*;
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Handmaid, Priest, Baron, Countess, Handmaid]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        assert_eq!(
+            Some(&RoundOutcome::new(
+                vec![PlayerId(0)],
+                RoundEndReason::DeckEmpty
+            )),
+            game.round_outcome()
+        );
+    }
+
+    #[test]
+    #[throws]
+    fn test_deck_empty_tie_broken_by_discards() {
+        use OriginalCard::*;
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Handmaid, Handmaid, Priest, Countess, Guard, Priest]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Guard, PlayerId(0), Some(PlayerId(2)), Some(Baron)),
+            &mut messenger,
+        )?;
+        game.perform_action(
+            CardAction::new(Priest, PlayerId(1), Some(PlayerId(0)), None),
+            &mut messenger,
+        )?;
+
+        assert_eq!(
+            Some(&RoundOutcome::new(
+                vec![PlayerId(1)],
+                RoundEndReason::DeckEmpty
+            )),
+            game.round_outcome()
+        );
+    }
+
+    #[test]
+    #[throws]
+    fn test_two_players() {
+        use OriginalCard::*;
+        let game = Game::with_deck(
+            original(),
+            2,
+            PlayerId(0),
+            deck(&[
+                Guard, Priest, Countess, Baron, Handmaid, King, Prince, Guard,
+            ]),
+        )?;
+
+        let burned = game
+            .burned_cards()
+            .iter()
+            .map(|c| OriginalCard::from_card(c.as_ref()).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(vec![Baron, Handmaid, King], burned);
+        assert_eq!(vec![Guard, Prince], hand_of(&game, 0));
+        assert_eq!(1, game.table.deck().cards_remaining());
+        assert!(game.to_string().contains("Face up: Baron, Handmaid, King"));
+    }
+
+    #[test]
+    #[throws]
+    fn test_card_ids_follow_cards() {
+        use OriginalCard::*;
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[King, Guard, Guard, Countess, Guard, Handmaid]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        // Player 2's Guard is the third card in the deck, and player 0's
+        // second Guard is the fifth.
+        game.perform_action(
+            CardAction::new(King, PlayerId(0), Some(PlayerId(2)), None),
+            &mut messenger,
+        )?;
+        assert_eq!(
+            Some(CardLocation::Discards(PlayerId(0))),
+            game.find_card(CardId(0))
+        );
+        assert_eq!(
+            Some(CardLocation::Hand(PlayerId(0))),
+            game.find_card(CardId(2))
+        );
+        assert_eq!(
+            Some(CardLocation::Hand(PlayerId(2))),
+            game.find_card(CardId(4))
+        );
+        assert_eq!(Some(CardLocation::OutCard), game.find_card(CardId(3)));
+    }
+
+    #[test]
+    #[throws]
+    fn test_seed() {
+        let first = GameBuilder::new().seed(7).build()?;
+        let second = GameBuilder::new().seed(7).build()?;
+        assert_eq!(first.to_string(), second.to_string());
+        assert_eq!(
+            first.table.deck().to_string(),
+            second.table.deck().to_string()
+        );
+    }
+
+    #[test]
+    #[throws]
+    fn test_player_names_and_seating() {
+        let game = GameBuilder::new()
+            .player_names(&["Ann", "Bo", "Cy"])
+            .seating(&[1, 2, 0])
+            .build()?;
+        assert_eq!("Bo", game.name_of(PlayerId(0)));
+        assert_eq!("Cy", game.name_of(PlayerId(1)));
+        assert_eq!("Ann", game.player(PlayerId(2))?.name());
+        assert_eq!(PlayerId(0), game.current_player());
+
+        assert_eq!(
+            Some(Error::DuplicatePlayerName("Ann".to_string())),
+            GameBuilder::new()
+                .player_names(&["Ann", "Bo", "Ann"])
+                .build()
+                .err()
+        );
+        assert_eq!(
+            Some(Error::InvalidSeating),
+            GameBuilder::new()
+                .player_names(&["Ann", "Bo", "Cy"])
+                .seating(&[0, 1])
+                .build()
+                .err()
+        );
+        assert_eq!(
+            Some(Error::InvalidNumberOfPlayers(5)),
+            GameBuilder::new()
+                .player_names(&["Ann", "Bo", "Cy", "Di", "Ed"])
+                .build()
+                .err()
+        );
+    }
+
+    #[test]
+    #[throws]
+    fn test_first_player() {
+        let players = vec![
+            PlayerProfile::new("Ann").last_date(12),
+            PlayerProfile::new("Bo"),
+            PlayerProfile::new("Cy").last_date(3),
+        ];
+        let game = GameBuilder::new()
+            .players(players.clone())
+            .first_player(FirstPlayer::MostRecentDate)
+            .build()?;
+        assert_eq!(PlayerId(2), game.current_player());
+        assert_eq!(2, game.player(PlayerId(2))?.hand().len());
+
+        // Seats are counted after the players are seated, so Cy is in seat 0.
+        let m = GameBuilder::new()
+            .players(players)
+            .seating(&[2, 1, 0])
+            .first_player(FirstPlayer::Seat(PlayerId(1)))
+            .build_match()?;
+        assert_eq!(PlayerId(1), m.game().current_player());
+        assert_eq!("Cy", m.game().name_of(PlayerId(0)));
+
+        let random = |seed| {
+            GameBuilder::new()
+                .num_players(4)
+                .first_player(FirstPlayer::Random)
+                .seed(seed)
+                .build()
+        };
+        // The same seed deals the same round, and a different seed deals a
+        // different one.
+        assert_eq!(random(3)?.current_player(), random(3)?.current_player());
+        assert_eq!(random(3)?.to_string(), random(3)?.to_string());
+        assert_ne!(random(3)?.to_string(), random(4)?.to_string());
+    }
+
+    #[test]
+    #[throws]
+    fn test_messages_use_names() {
+        use OriginalCard::*;
+        let table = Table::with_names(&["Ann", "Bo", "Cy"])?;
+        let mut game = Game::with_stacked_deck(
+            original(),
+            table,
+            PlayerId(0),
+            deck(&[Priest, Handmaid, Baron, Countess, Guard, Guard]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Priest, PlayerId(0), Some(PlayerId(2)), None),
+            &mut messenger,
+        )?;
+        assert!(messenger
+            .messages
+            .contains(&"All: Ann plays a Priest on Cy".to_string()));
+        assert!(messenger
+            .messages
+            .contains(&"Ann: Cy shows you a Baron".to_string()));
+
+        let error = game
+            .perform_action(
+                CardAction::new(Guard, PlayerId(0), Some(PlayerId(2)), Some(Priest)),
+                &mut messenger,
+            )
+            .err()
+            .unwrap();
+        assert_eq!(
+            "Only the current player can take an action. Ann provided.",
+            game.describe_error(&error)
+        );
+    }
+
+    #[test]
+    fn test_describe_error() {
+        let name_of = |id: PlayerId| ["Ann", "Bo"][id.index()].to_string();
+        let error = Error::BadActionPlayerDoesntHaveCard(PlayerId(1), "Guard".to_string());
+        assert_eq!("Player 2 does not have card, Guard", error.to_string());
+        assert_eq!("Bo does not have card, Guard", error.describe(name_of));
+        assert_eq!(
+            "The round is over",
+            Error::BadActionRoundOver.describe(name_of)
+        );
+    }
+
+    #[test]
+    #[throws]
+    fn test_audit() {
+        for seed in 0..20 {
+            GameBuilder::new()
+                .num_players(2)
+                .seed(seed)
+                .build()?
+                .audit()?;
+            GameBuilder::new()
+                .num_players(8)
+                .ruleset(PremiumRuleset)
+                .seed(seed)
+                .build()?
+                .audit()?;
+        }
+
+        use OriginalCard::*;
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Guard, Priest, Baron, Countess, Handmaid, King, Prince]),
+        )?;
+        let mut messenger = TestMessenger::new();
+        game.perform_action(
+            CardAction::new(Guard, PlayerId(0), Some(PlayerId(1)), Some(Priest)),
+            &mut messenger,
+        )?;
+        game.audit()?;
+
+        // An eliminated player is dealt a card.
+        game.deal_one_to_player(PlayerId(1))?;
+        assert_eq!(
+            Some(Error::InternalErrorInvariantViolated(
+                "Player 2 holds 1 cards".to_string()
+            )),
+            game.audit().err()
+        );
+
+        // A deck with an extra Princess doesn't match the original edition.
+        let mut cards = OriginalCard::deck();
+        let mut cards = cards.deal(cards.cards_remaining());
+        cards.push(Box::new(Princess));
+        let game = Game::with_table(original(), Table::new(2)?, PlayerId(0), Deck::new(cards))?;
+        let error = game.audit().err().unwrap().to_string();
+        assert!(error.starts_with("Internal error: Princess"));
+        assert!(error.ends_with("is not in the deck"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Player 1 holds 2 cards")]
+    #[cfg(debug_assertions)]
+    fn test_audit_after_action() {
+        use OriginalCard::*;
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Handmaid, Priest, Baron, Countess, Guard, King, Prince]),
+        )
+        .unwrap();
+        let card = game.table.deck_mut().deal_one().unwrap();
+        game.player_mut(PlayerId(0)).unwrap().add_card_to_hand(card);
+
+        let _ = game.perform_action(
+            CardAction::new(Handmaid, PlayerId(0), None, None),
+            &mut TestMessenger::new(),
+        );
+    }
+
+    #[derive(Debug)]
+    struct FivePlayerHouseRules;
+
+    impl Ruleset for FivePlayerHouseRules {
+        fn id(&self) -> &'static str {
+            "five-player-house-rules"
+        }
+
+        fn deck(&self) -> Deck {
+            OriginalRuleset.deck()
+        }
+
+        fn player_counts(&self) -> std::ops::RangeInclusive<usize> {
+            3..=5
+        }
+
+        fn tokens_to_win(&self, _num_players: usize) -> usize {
+            3
+        }
+
+        #[throws]
+        fn rules_for_card(&self, card: &dyn Card) -> &CardRules {
+            OriginalRuleset.rules_for_card(card)?
+        }
+
+        #[throws]
+        fn perform_card_action(
+            &self,
+            action: &CardAction,
+            game: &mut Game,
+            messenger: &mut dyn Messenger,
+        ) {
+            // Discarding the Princess to a Prince doesn't knock a player out.
+            // Only Game's public methods are used, as they would be outside
+            // this crate.
+            if OriginalCard::Prince.is_same_card(action.card()) {
+                let target = action.target()?;
+                let card = game.hand_card(target)?;
+                messenger.event(
+                    game,
+                    &GameEvent::HandDiscarded {
+                        player: target,
+                        card,
+                    },
+                );
+                game.discard_hand(target)?;
+                game.redraw(target)?;
+            } else {
+                OriginalRuleset.perform_card_action(action, game, messenger)?
+            }
+        }
+    }
+
+    #[test]
+    #[throws]
+    fn test_ruleset() {
+        assert_eq!(
+            Some(Error::InvalidNumberOfPlayers(5)),
+            GameBuilder::new().num_players(5).build().err()
+        );
+
+        let game = GameBuilder::new()
+            .num_players(5)
+            .ruleset(FivePlayerHouseRules)
+            .build()?;
+        assert_eq!("five-player-house-rules", game.ruleset().id());
+        assert_eq!(16 - 5 - 1 - 1, game.table.deck().cards_remaining());
+
+        let m = GameBuilder::new()
+            .num_players(5)
+            .ruleset(FivePlayerHouseRules)
+            .build_match()?;
+        assert_eq!(3, m.tokens_to_win());
+    }
+
+    #[test]
+    #[throws]
+    fn test_ruleset_card_effect() {
+        use OriginalCard::*;
+        let mut game = Game::with_deck(
+            Rc::new(FivePlayerHouseRules),
+            3,
+            PlayerId(0),
+            deck(&[Prince, Princess, Guard, Baron, Handmaid, Priest, King]),
+        )?;
+        let mut messenger = TestMessenger::new();
+        game.perform_action(
+            CardAction::new(Prince, PlayerId(0), Some(PlayerId(1)), None),
+            &mut messenger,
+        )?;
+        assert!(game.is_active(PlayerId(1)));
+        assert_eq!(vec![Priest, King], hand_of(&game, 1));
+        assert_eq!(
+            vec![Princess],
+            game.player(PlayerId(1))?
+                .discards()
+                .iter()
+                .filter_map(|c| OriginalCard::from_card(c.as_ref()))
+                .collect::<Vec<_>>()
+        );
+    }
+}
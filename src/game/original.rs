@@ -1,177 +1,583 @@
 use crate::game::card_action::CardAction;
 use crate::game::card_rules::CardRules;
+use crate::game::game_event::{EliminationReason, GameEvent};
+use crate::game::ruleset::Ruleset;
 use crate::messenger::Messenger;
-use crate::state::Card;
+use crate::state::{Card, Deck, OriginalCard};
 use crate::{Error, Game};
-use fehler::throws;
+use fehler::{throw, throws};
+use std::ops::RangeInclusive;
 
 const NO_TARGET: CardRules = CardRules::new(false, false, false);
 const TARGET_REQUIRED: CardRules = CardRules::new(true, false, false);
-const GUESS_REQUIRED: CardRules = CardRules::new(true, false, true);
+const GUESS_REQUIRED: CardRules = CardRules::new(true, false, true).forbid_guess("Guard");
 const TARGET_SELF_ALLOWED: CardRules = CardRules::new(true, true, false);
 
-pub fn rules_for_card(card: Card) -> &'static CardRules {
-    match card {
-        Card::Guard => &GUESS_REQUIRED,
-        Card::Priest => &TARGET_REQUIRED,
-        Card::Baron => &TARGET_REQUIRED,
-        Card::Handmaid => &NO_TARGET,
-        Card::Prince => &TARGET_SELF_ALLOWED,
-        Card::King => &TARGET_REQUIRED,
-        Card::Countess => &NO_TARGET,
-        Card::Princess => &NO_TARGET,
+// Holding the Countess with the King or Prince forces the Countess to be played.
+const PRINCE: CardRules = TARGET_SELF_ALLOWED.must_play_instead("Countess");
+const KING: CardRules = TARGET_REQUIRED.must_play_instead("Countess");
+
+/// The original, 16-card edition of Love Letter, for 2 to 4 players.
+#[derive(Debug)]
+pub struct OriginalRuleset;
+
+impl Ruleset for OriginalRuleset {
+    fn id(&self) -> &'static str {
+        "original"
+    }
+
+    fn deck(&self) -> Deck {
+        OriginalCard::deck()
+    }
+
+    fn player_counts(&self) -> RangeInclusive<usize> {
+        2..=4
+    }
+
+    fn tokens_to_win(&self, num_players: usize) -> usize {
+        match num_players {
+            2 => 7,
+            3 => 5,
+            _ => 4,
+        }
+    }
+
+    #[throws]
+    fn rules_for_card(&self, card: &dyn Card) -> &CardRules {
+        rules_for_card(card)?
+    }
+
+    #[throws]
+    fn perform_card_action(
+        &self,
+        action: &CardAction,
+        game: &mut Game,
+        messenger: &mut dyn Messenger,
+    ) {
+        perform_card_action(action, game, messenger)?
+    }
+}
+
+#[throws]
+fn original_card(card: &dyn Card) -> OriginalCard {
+    if let Some(original) = OriginalCard::from_card(card) {
+        original
+    } else {
+        throw!(Error::BadActionUnknownCard(card.name().to_string()));
+    }
+}
+
+#[throws]
+fn rules_for_card(card: &dyn Card) -> &'static CardRules {
+    match original_card(card)? {
+        OriginalCard::Guard => &GUESS_REQUIRED,
+        OriginalCard::Priest => &TARGET_REQUIRED,
+        OriginalCard::Baron => &TARGET_REQUIRED,
+        OriginalCard::Handmaid => &NO_TARGET,
+        OriginalCard::Prince => &PRINCE,
+        OriginalCard::King => &KING,
+        OriginalCard::Countess => &NO_TARGET,
+        OriginalCard::Princess => &NO_TARGET,
     }
 }
 
 #[throws]
-pub fn perform_card_action(action: &CardAction, game: &mut Game, messenger: &mut impl Messenger) {
-    match action.card() {
-        Card::Guard => guard_action(action, game, messenger)?,
-        Card::Priest => priest_action(action, game, messenger)?,
-        Card::Baron => baron_action(action, game, messenger)?,
-        Card::Handmaid => handmaid_action(action, game, messenger)?,
-        Card::Prince => prince_action(action, game, messenger)?,
-        Card::King => king_action(action, game, messenger)?,
-        Card::Countess => countess_action(action, game, messenger)?,
-        Card::Princess => princess_action(action, game, messenger)?,
+fn perform_card_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    if rules_for_card(action.card())?.target_required() && !action.has_target() {
+        // Every other player is protected, so the card has no effect.
+        send_no_target_message(action, game, messenger)?;
+    } else {
+        perform_targeted_card_action(action, game, messenger)?;
+    }
+}
+
+#[throws]
+fn perform_targeted_card_action(
+    action: &CardAction,
+    game: &mut Game,
+    messenger: &mut dyn Messenger,
+) {
+    match original_card(action.card())? {
+        OriginalCard::Guard => guard_action(action, game, messenger)?,
+        OriginalCard::Priest => priest_action(action, game, messenger)?,
+        OriginalCard::Baron => baron_action(action, game, messenger)?,
+        OriginalCard::Handmaid => handmaid_action(action, game, messenger)?,
+        OriginalCard::Prince => prince_action(action, game, messenger)?,
+        OriginalCard::King => king_action(action, game, messenger)?,
+        OriginalCard::Countess => countess_action(action, game, messenger)?,
+        OriginalCard::Princess => princess_action(action, game, messenger)?,
     }
 }
 
 #[throws]
-fn guard_action(action: &CardAction, game: &mut Game, messenger: &mut impl Messenger) {
-    let target_index = action.target()?;
+pub(super) fn guard_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    let target_id = action.target()?;
     let guess = action.guess()?;
-    let target = game.player(target_index)?;
-
-    messenger.to_all(&format!(
-        "Player {} guesses that Player {} has a {}",
-        action.current(),
-        target_index,
-        guess
-    ));
-
-    let target_card = target.card_in_hand()?;
-    if guess == target_card {
-        messenger.to_all(&format!(
-            "Player {} has a {} and is out!",
-            target_index, guess
-        ));
-        game.make_inactive(target_index)?;
+
+    messenger.event(
+        game,
+        &GameEvent::GuessMade {
+            player: action.current(),
+            card: action.boxed_card(),
+            target: target_id,
+            guess: action.boxed_guess()?,
+            by_value: false,
+        },
+    );
+
+    if guess.is_same_card(game.player(target_id)?.card_in_hand()?) {
+        game.make_inactive(target_id)?;
+        messenger.event(
+            game,
+            &GameEvent::PlayerEliminated {
+                player: target_id,
+                reason: EliminationReason::CardGuessed(action.boxed_guess()?),
+            },
+        );
     } else {
-        messenger.to_all(&format!(
-            "Player {} does not have a {}",
-            target_index, guess
-        ));
+        messenger.event(
+            game,
+            &GameEvent::GuessMissed {
+                target: target_id,
+                guess: action.boxed_guess()?,
+                by_value: false,
+            },
+        );
     }
 }
 
 #[throws]
-fn priest_action(action: &CardAction, game: &mut Game, messenger: &mut impl Messenger) {
-    let target_index = action.target()?;
-    let target = game.player(target_index)?;
-
-    send_card_played_with_target_message(action, messenger)?;
-
-    messenger.to_all(&format!(
-        "Player {} shows his card to Player {}",
-        target_index,
-        action.current()
-    ));
-
-    let target_card = target.card_in_hand()?;
-    messenger.to_player(
-        game.player(action.current())?,
-        &format!("Player {} shows you a {}", target_index, target_card),
+pub(super) fn priest_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    let current_id = action.current();
+    let target_id = action.target()?;
+
+    send_card_played_with_target_message(action, game, messenger)?;
+
+    messenger.event(
+        game,
+        &GameEvent::HandShown {
+            owner: target_id,
+            viewer: current_id,
+            card: action.boxed_card(),
+        },
+    );
+    messenger.event(
+        game,
+        &GameEvent::CardRevealed {
+            owner: target_id,
+            viewer: current_id,
+            card: game.hand_card(target_id)?,
+        },
     );
+    game.reveal_hand(current_id, target_id)?;
 }
 
 #[throws]
-fn send_card_played_with_target_message(action: &CardAction, messenger: &mut impl Messenger) {
-    messenger.to_all(&format!(
-        "Player {} plays a {} on Player {}",
-        action.current(),
-        action.card(),
-        action.target()?
-    ));
+pub(super) fn send_card_played_with_target_message(
+    action: &CardAction,
+    game: &Game,
+    messenger: &mut dyn Messenger,
+) {
+    messenger.event(
+        game,
+        &GameEvent::CardPlayed {
+            player: action.current(),
+            card: action.boxed_card(),
+            targets: vec![action.target()?],
+        },
+    );
 }
 
 #[throws]
-fn send_card_played_message(action: &CardAction, messenger: &mut impl Messenger) {
-    messenger.to_all(&format!(
-        "Player {} plays a {}",
-        action.current(),
-        action.card()
-    ));
+pub(super) fn send_no_target_message(
+    action: &CardAction,
+    game: &Game,
+    messenger: &mut dyn Messenger,
+) {
+    messenger.event(
+        game,
+        &GameEvent::NoTarget {
+            player: action.current(),
+            card: action.boxed_card(),
+        },
+    );
 }
 
 #[throws]
-fn baron_action(action: &CardAction, game: &mut Game, messenger: &mut impl Messenger) {
-    send_card_played_with_target_message(action, messenger)?;
+pub(super) fn send_card_played_message(
+    action: &CardAction,
+    game: &Game,
+    messenger: &mut dyn Messenger,
+) {
+    messenger.event(
+        game,
+        &GameEvent::CardPlayed {
+            player: action.current(),
+            card: action.boxed_card(),
+            targets: vec![],
+        },
+    );
+}
 
-    let current_index = action.current();
-    let target_index = action.target()?;
-    let current_player = game.player(current_index)?;
-    let target_player = game.player(target_index)?;
+#[throws]
+pub(super) fn baron_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    send_card_played_with_target_message(action, game, messenger)?;
 
-    let player_card = current_player.card_in_hand()?;
-    let target_card = target_player.card_in_hand()?;
+    let current_id = action.current();
+    let target_id = action.target()?;
+    let player_card = game.hand_card(current_id)?;
+    let target_card = game.hand_card(target_id)?;
+    // The players show each other their hands.
+    game.reveal_hand(current_id, target_id)?;
+    game.reveal_hand(target_id, current_id)?;
 
-    if player_card == target_card {
-        messenger.to_all(&format!("Boingy, boingy, boingy. (The cards are equal.)"));
+    let loser = if player_card.has_same_value(target_card.as_ref()) {
+        None
+    } else if player_card.value() > target_card.value() {
+        Some((target_id, target_card))
     } else {
-        let (out_index, out_card) = if player_card > target_card {
-            (target_index, target_card)
-        } else {
-            (current_index, player_card)
-        };
-        messenger.to_all(&format!(
-            "Player {} showed a {} and is out.",
-            out_index, out_card
-        ));
-        game.make_inactive(out_index)?;
+        Some((current_id, player_card))
+    };
+    messenger.event(
+        game,
+        &GameEvent::CardsCompared {
+            player: current_id,
+            card: action.boxed_card(),
+            target: target_id,
+            loser: loser.as_ref().map(|(id, _)| *id),
+        },
+    );
+    if let Some((out_id, out_card)) = loser {
+        game.make_inactive(out_id)?;
+        messenger.event(
+            game,
+            &GameEvent::PlayerEliminated {
+                player: out_id,
+                reason: EliminationReason::LostComparison(out_card),
+            },
+        );
     }
 }
 
 #[throws]
-fn handmaid_action(action: &CardAction, game: &mut Game, messenger: &mut impl Messenger) {
-    send_card_played_message(action, messenger)?;
-    messenger.to_all(&format!("Player {} is safe.", action.current()));
+pub(super) fn handmaid_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    send_card_played_message(action, game, messenger)?;
     game.make_protected(action.current())?;
+    messenger.event(
+        game,
+        &GameEvent::PlayerProtected {
+            player: action.current(),
+        },
+    );
 }
 
 #[throws]
-fn prince_action(action: &CardAction, game: &mut Game, messenger: &mut impl Messenger) {
-    //    check_valid_target(action)?;
-    send_card_played_with_target_message(action, messenger)?;
+pub(super) fn prince_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    send_card_played_with_target_message(action, game, messenger)?;
 
-    let current_index = action.current();
-    let target_index = action.target()?;
-    let current_player = game.player(current_index)?;
-    let target_player = game.player(target_index)?;
-
-    let target_card = target_player.card_in_hand()?;
-    messenger.to_all(&format!(
-        "Player {} discards a {}.",
-        target_index, target_card
-    ));
-    if let Card::Princess = target_card {
-        messenger.to_all(&format!("Player {} is out!", target_index));
+    let target_id = action.target()?;
+    let target_card = game.hand_card(target_id)?;
+    messenger.event(
+        game,
+        &GameEvent::HandDiscarded {
+            player: target_id,
+            card: target_card.clone(),
+        },
+    );
+    // The Princess has a different value in some editions, so match on her name.
+    if target_card.name() == OriginalCard::Princess.name() {
+        game.make_inactive(target_id)?;
+        messenger.event(
+            game,
+            &GameEvent::PlayerEliminated {
+                player: target_id,
+                reason: EliminationReason::PrincessDiscarded,
+            },
+        );
+    } else {
+        game.discard_hand(target_id)?;
+        game.redraw(target_id)?;
+        messenger.event(
+            game,
+            &GameEvent::CardDrawn {
+                player: target_id,
+                card: game.hand_card(target_id)?,
+            },
+        );
     }
-
-    todo!()
 }
 
 #[throws]
-fn king_action(action: &CardAction, game: &mut Game, messenger: &mut impl Messenger) {
-    todo!()
+pub(super) fn king_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    send_card_played_with_target_message(action, game, messenger)?;
+
+    let current_id = action.current();
+    let target_id = action.target()?;
+    game.swap_hands(current_id, target_id)?;
+
+    messenger.event(
+        game,
+        &GameEvent::HandsSwapped {
+            first: current_id,
+            second: target_id,
+        },
+    );
+    for (receiver, giver) in &[(current_id, target_id), (target_id, current_id)] {
+        messenger.event(
+            game,
+            &GameEvent::CardReceived {
+                player: *receiver,
+                from: *giver,
+                card: game.hand_card(*receiver)?,
+            },
+        );
+    }
 }
 
 #[throws]
-fn countess_action(action: &CardAction, game: &mut Game, messenger: &mut impl Messenger) {
-    todo!()
+pub(super) fn countess_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    send_card_played_message(action, game, messenger)?;
 }
 
 #[throws]
-fn princess_action(action: &CardAction, game: &mut Game, messenger: &mut impl Messenger) {
-    todo!()
+pub(super) fn princess_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    send_card_played_message(action, game, messenger)?;
+    game.make_inactive(action.current())?;
+    messenger.event(
+        game,
+        &GameEvent::PlayerEliminated {
+            player: action.current(),
+            reason: EliminationReason::PrincessPlayed,
+        },
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::test::{deck, hand_of, original};
+    use crate::game::{Audience, RoundEndReason, RoundOutcome};
+    use crate::messenger::test::TestMessenger;
+    use crate::state::PlayerId;
+    use OriginalCard::*;
+
+    #[test]
+    #[throws]
+    fn test_prince() {
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Prince, Baron, Guard, Countess, Handmaid, Priest, King]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Prince, PlayerId(0), Some(PlayerId(1)), None),
+            &mut messenger,
+        )?;
+        assert!(game.is_active(PlayerId(1)));
+        assert!(Baron.is_same_card(game.player(PlayerId(1))?.discards()[0].as_ref()));
+        assert_eq!(vec![Priest, King], hand_of(&game, 1));
+    }
+
+    #[test]
+    #[throws]
+    fn test_prince_takes_out_card_from_empty_deck() {
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Prince, Baron, Guard, Countess, Handmaid]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Prince, PlayerId(0), Some(PlayerId(1)), None),
+            &mut messenger,
+        )?;
+        assert_eq!(vec![Countess], hand_of(&game, 1));
+        assert!(game.table.out_card().is_none());
+        assert_eq!(
+            Some(&RoundOutcome::new(
+                vec![PlayerId(1)],
+                RoundEndReason::DeckEmpty
+            )),
+            game.round_outcome()
+        );
+    }
+
+    #[test]
+    #[throws]
+    fn test_prince_forces_princess_discard() {
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Prince, Princess, Guard, Countess, Handmaid, Priest]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Prince, PlayerId(0), Some(PlayerId(1)), None),
+            &mut messenger,
+        )?;
+        assert!(!game.is_active(PlayerId(1)));
+        assert!(game.player(PlayerId(1))?.hand().is_empty());
+        assert_eq!(PlayerId(2), game.current_player());
+    }
+
+    #[test]
+    #[throws]
+    fn test_king() {
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[King, Baron, Guard, Countess, Priest, Handmaid]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(King, PlayerId(0), Some(PlayerId(2)), None),
+            &mut messenger,
+        )?;
+        assert_eq!(vec![Guard], hand_of(&game, 0));
+        assert_eq!(vec![Baron, Handmaid], hand_of(&game, 1));
+        assert_eq!(vec![Priest], hand_of(&game, 2));
+
+        let events = &messenger.events;
+        assert!(matches!(
+            &events[1],
+            GameEvent::CardPlayed { player: PlayerId(0), targets, .. } if targets == &[PlayerId(2)]
+        ));
+        assert!(matches!(
+            events[2],
+            GameEvent::HandsSwapped {
+                first: PlayerId(0),
+                second: PlayerId(2)
+            }
+        ));
+        assert!(matches!(
+            &events[4],
+            GameEvent::CardReceived { player: PlayerId(2), from: PlayerId(0), card }
+                if Priest.is_same_card(card.as_ref())
+        ));
+        assert_eq!(
+            Audience::Player(PlayerId(2)),
+            messenger.events[4].audience()
+        );
+        assert!(messenger
+            .messages
+            .contains(&"Player 3: Player 1 gives you a Priest.".to_string()));
+    }
+
+    #[test]
+    #[throws]
+    fn test_countess() {
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Countess, Baron, Guard, Priest, Handmaid, Handmaid]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Countess, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        assert!(game.is_active(PlayerId(0)));
+        assert_eq!(vec![Handmaid], hand_of(&game, 0));
+    }
+
+    #[test]
+    #[throws]
+    fn test_princess() {
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Princess, Baron, Guard, Countess, Handmaid, Priest]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Princess, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        assert!(!game.is_active(PlayerId(0)));
+        assert!(game.player(PlayerId(0))?.hand().is_empty());
+        assert_eq!(2, game.player(PlayerId(0))?.discards().len());
+    }
+
+    #[test]
+    #[throws]
+    fn test_countess_must_be_played_with_king() {
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[King, Baron, Guard, Priest, Countess, Handmaid]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        assert_eq!(
+            Some(Error::BadActionMustPlayCard("Countess".to_string())),
+            game.perform_action(
+                CardAction::new(King, PlayerId(0), Some(PlayerId(1)), None),
+                &mut messenger
+            )
+            .err()
+        );
+        game.perform_action(
+            CardAction::new(Countess, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        assert_eq!(vec![King], hand_of(&game, 0));
+    }
+
+    #[test]
+    #[throws]
+    fn test_no_valid_target() {
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[
+                Handmaid, Guard, Handmaid, Priest, Handmaid, Baron, Handmaid, King,
+            ]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        game.perform_action(
+            CardAction::new(Baron, PlayerId(1), Some(PlayerId(2)), None),
+            &mut messenger,
+        )?;
+        // Player 1 is out. Player 2 protects themselves, then player 0 can't target anyone.
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(2), None, None),
+            &mut messenger,
+        )?;
+        assert!(game
+            .perform_action(
+                CardAction::new(King, PlayerId(0), Some(PlayerId(2)), None),
+                &mut messenger
+            )
+            .is_err());
+        game.perform_action(
+            CardAction::new(King, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+
+        assert_eq!(vec![Handmaid], hand_of(&game, 0));
+        assert!(messenger.messages.contains(
+            &"All: Player 1 plays a King, but there is no one to target. It has no effect."
+                .to_string()
+        ));
+    }
 }
@@ -0,0 +1,251 @@
+use crate::game::RoundOutcome;
+use crate::state::{Card, PlayerId};
+use crate::{Error, Game};
+use fehler::throws;
+
+/// What everyone at the table can see of one player.
+#[derive(Clone, Debug)]
+pub struct PlayerSummary {
+    id: PlayerId,
+    name: String,
+    hand_size: usize,
+    discards: Vec<Box<dyn Card>>,
+    active: bool,
+    protected: bool,
+}
+
+impl PlayerSummary {
+    pub fn id(&self) -> PlayerId {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The number of cards in the player's hand. The cards themselves are
+    /// hidden.
+    pub fn hand_size(&self) -> usize {
+        self.hand_size
+    }
+
+    pub fn discards(&self) -> &[Box<dyn Card>] {
+        &self.discards
+    }
+
+    /// Returns false if the player has been eliminated from the round.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
+}
+
+/// A card in another player's hand that the viewer was shown, by a Priest
+/// or a similar card. The owner may no longer hold it.
+#[derive(Clone, Debug)]
+pub struct RevealedCard {
+    owner: PlayerId,
+    card: Box<dyn Card>,
+}
+
+impl RevealedCard {
+    /// The player whose hand held the card.
+    pub fn owner(&self) -> PlayerId {
+        self.owner
+    }
+
+    pub fn card(&self) -> &dyn Card {
+        self.card.as_ref()
+    }
+}
+
+/// A snapshot of exactly what one player may see of a game. Other players'
+/// hands, the order of the deck, and the card set aside are never included.
+#[derive(Clone, Debug)]
+pub struct PlayerView {
+    player: PlayerId,
+    hand: Vec<Box<dyn Card>>,
+    players: Vec<PlayerSummary>,
+    deck_count: usize,
+    burned: Vec<Box<dyn Card>>,
+    current_player: PlayerId,
+    forced_target: Option<PlayerId>,
+    cards_to_return: usize,
+    revealed: Vec<RevealedCard>,
+    outcome: Option<RoundOutcome>,
+}
+
+impl PlayerView {
+    /// The player who this view is for.
+    pub fn player(&self) -> PlayerId {
+        self.player
+    }
+
+    /// The viewer's own hand.
+    pub fn hand(&self) -> &[Box<dyn Card>] {
+        &self.hand
+    }
+
+    /// Every player at the table, including the viewer, in seating order.
+    pub fn players(&self) -> &[PlayerSummary] {
+        &self.players
+    }
+
+    /// The number of cards left in the deck.
+    pub fn deck_count(&self) -> usize {
+        self.deck_count
+    }
+
+    /// The cards removed face up at the start of the round.
+    pub fn burned_cards(&self) -> &[Box<dyn Card>] {
+        &self.burned
+    }
+
+    pub fn current_player(&self) -> PlayerId {
+        self.current_player
+    }
+
+    pub fn forced_target(&self) -> Option<PlayerId> {
+        self.forced_target
+    }
+
+    /// The number of cards that the current player must return to the deck.
+    pub fn cards_to_return(&self) -> usize {
+        self.cards_to_return
+    }
+
+    /// The cards that the viewer has been shown from other players' hands
+    /// this round, in the order they were shown.
+    pub fn revealed(&self) -> &[RevealedCard] {
+        &self.revealed
+    }
+
+    pub fn round_outcome(&self) -> Option<&RoundOutcome> {
+        self.outcome.as_ref()
+    }
+}
+
+impl Game {
+    /// Returns what the player may see of the game.
+    #[throws]
+    pub fn view_for(&self, player_id: PlayerId) -> PlayerView {
+        let hand = self.player(player_id)?.hand().to_vec();
+
+        let mut players = Vec::new();
+        for id in self.table.player_ids() {
+            let player = self.player(id)?;
+            players.push(PlayerSummary {
+                id,
+                name: player.name().to_string(),
+                hand_size: player.hand().len(),
+                discards: player.discards().to_vec(),
+                active: self.is_active(id),
+                protected: self.is_protected(id),
+            });
+        }
+
+        let revealed = self
+            .revealed
+            .iter()
+            .filter(|(viewer, _, _)| *viewer == player_id)
+            .map(|(_, owner, card)| RevealedCard {
+                owner: *owner,
+                card: card.clone(),
+            })
+            .collect();
+
+        PlayerView {
+            player: player_id,
+            hand,
+            players,
+            deck_count: self.table.deck().cards_remaining(),
+            burned: self.burned_cards().to_vec(),
+            current_player: self.current_player,
+            forced_target: self.forced_target,
+            cards_to_return: self.cards_to_return,
+            revealed,
+            outcome: self.outcome.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::test::{deck, original};
+    use crate::game::CardAction;
+    use crate::messenger::test::TestMessenger;
+    use crate::state::OriginalCard::*;
+
+    #[test]
+    #[throws]
+    fn test_view_for() {
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[
+                Priest, Handmaid, Baron, Countess, Guard, Guard, King, Prince,
+            ]),
+        )?;
+        let mut messenger = TestMessenger::new();
+        game.perform_action(
+            CardAction::new(Priest, PlayerId(0), Some(PlayerId(2)), None),
+            &mut messenger,
+        )?;
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(1), None, None),
+            &mut messenger,
+        )?;
+
+        let view = game.view_for(PlayerId(0))?;
+        assert_eq!(PlayerId(0), view.player());
+        assert_eq!(1, view.hand().len());
+        assert!(Guard.is_same_card(view.hand()[0].as_ref()));
+        assert_eq!(1, view.deck_count());
+        assert_eq!(PlayerId(2), view.current_player());
+
+        let players = view.players();
+        assert_eq!(3, players.len());
+        assert_eq!("Player 2", players[1].name());
+        assert!(players[1].is_protected());
+        assert!(Priest.is_same_card(players[0].discards()[0].as_ref()));
+        assert_eq!(2, players[2].hand_size());
+        assert!(players.iter().all(|p| p.is_active()));
+
+        assert_eq!(1, view.revealed().len());
+        assert_eq!(PlayerId(2), view.revealed()[0].owner());
+        assert!(Baron.is_same_card(view.revealed()[0].card()));
+
+        // Nobody else saw the Baron.
+        assert!(game.view_for(PlayerId(1))?.revealed().is_empty());
+        assert!(game.view_for(PlayerId(2))?.revealed().is_empty());
+
+        assert_eq!(
+            Some(Error::InvalidPlayerNumber(PlayerId(3))),
+            game.view_for(PlayerId(3)).err()
+        );
+
+        // A Baron shows both players each other's hands, even when they tie.
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Baron, Guard, Priest, Countess, Guard, King, Prince]),
+        )?;
+        game.perform_action(
+            CardAction::new(Baron, PlayerId(0), Some(PlayerId(1)), None),
+            &mut messenger,
+        )?;
+        for (viewer, owner) in &[(0, 1), (1, 0)] {
+            let view = game.view_for(PlayerId(*viewer))?;
+            assert_eq!(1, view.revealed().len());
+            assert_eq!(PlayerId(*owner), view.revealed()[0].owner());
+            assert!(Guard.is_same_card(view.revealed()[0].card()));
+        }
+        assert!(game.view_for(PlayerId(2))?.revealed().is_empty());
+    }
+}
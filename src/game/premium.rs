@@ -0,0 +1,710 @@
+use crate::game::card_action::CardAction;
+use crate::game::card_rules::{CardRules, SecondTarget};
+use crate::game::game_event::{EliminationReason, GameEvent};
+use crate::game::original::{
+    baron_action, countess_action, handmaid_action, king_action, priest_action, prince_action,
+    princess_action, send_card_played_message, send_card_played_with_target_message,
+    send_no_target_message,
+};
+use crate::game::round_outcome::{RoundEndReason, RoundOutcome};
+use crate::game::ruleset::{highest_hands, Ruleset};
+use crate::messenger::Messenger;
+use crate::state::{Card, Deck, Player, PlayerId, PremiumCard};
+use crate::{Error, Game};
+use fehler::{throw, throws};
+use std::ops::RangeInclusive;
+
+const NO_TARGET: CardRules = CardRules::new(false, false, false);
+const TARGET_REQUIRED: CardRules = CardRules::new(true, false, false);
+const TARGET_SELF_ALLOWED: CardRules = CardRules::new(true, true, false);
+// In the premium edition, Guards and Bishops name a number other than 1.
+const GUESS_REQUIRED: CardRules = CardRules::new(true, false, true).forbid_guess("Guard");
+
+const PRINCE: CardRules = TARGET_SELF_ALLOWED.must_play_instead("Countess");
+const KING: CardRules = TARGET_REQUIRED.must_play_instead("Countess");
+const CARDINAL: CardRules = TARGET_SELF_ALLOWED.with_second_target(SecondTarget::Required);
+const BARONESS: CardRules = TARGET_REQUIRED.with_second_target(SecondTarget::Optional);
+
+/// The premium edition of Love Letter, with the 32-card deck, for 5 to 8
+/// players.
+///
+/// A player who plays the Cardinal on two other players chooses which of
+/// the two hands to look at by naming that player as the first target.
+#[derive(Debug)]
+pub struct PremiumRuleset;
+
+impl Ruleset for PremiumRuleset {
+    fn id(&self) -> &'static str {
+        "premium"
+    }
+
+    fn deck(&self) -> Deck {
+        PremiumCard::deck()
+    }
+
+    fn player_counts(&self) -> RangeInclusive<usize> {
+        5..=8
+    }
+
+    fn tokens_to_win(&self, _num_players: usize) -> usize {
+        3
+    }
+
+    #[throws]
+    fn rules_for_card(&self, card: &dyn Card) -> &CardRules {
+        match premium_card(card)? {
+            PremiumCard::Jester => &TARGET_REQUIRED,
+            PremiumCard::Assassin => &NO_TARGET,
+            PremiumCard::Guard => &GUESS_REQUIRED,
+            PremiumCard::Cardinal => &CARDINAL,
+            PremiumCard::Priest => &TARGET_REQUIRED,
+            PremiumCard::Baroness => &BARONESS,
+            PremiumCard::Baron => &TARGET_REQUIRED,
+            PremiumCard::Sycophant => &TARGET_SELF_ALLOWED,
+            PremiumCard::Handmaid => &NO_TARGET,
+            PremiumCard::Count => &NO_TARGET,
+            PremiumCard::Prince => &PRINCE,
+            PremiumCard::Constable => &NO_TARGET,
+            PremiumCard::King => &KING,
+            PremiumCard::DowagerQueen => &TARGET_REQUIRED,
+            PremiumCard::Countess => &NO_TARGET,
+            PremiumCard::Princess => &NO_TARGET,
+            PremiumCard::Bishop => &GUESS_REQUIRED,
+        }
+    }
+
+    #[throws]
+    fn perform_card_action(
+        &self,
+        action: &CardAction,
+        game: &mut Game,
+        messenger: &mut dyn Messenger,
+    ) {
+        if self.rules_for_card(action.card())?.target_required() && !action.has_target() {
+            send_no_target_message(action, game, messenger)?;
+        } else {
+            match premium_card(action.card())? {
+                PremiumCard::Jester => jester_action(action, game, messenger)?,
+                PremiumCard::Assassin => send_card_played_message(action, game, messenger)?,
+                PremiumCard::Guard => guard_action(action, game, messenger)?,
+                PremiumCard::Cardinal => cardinal_action(action, game, messenger)?,
+                PremiumCard::Priest => priest_action(action, game, messenger)?,
+                PremiumCard::Baroness => baroness_action(action, game, messenger)?,
+                PremiumCard::Baron => baron_action(action, game, messenger)?,
+                PremiumCard::Sycophant => sycophant_action(action, game, messenger)?,
+                PremiumCard::Handmaid => handmaid_action(action, game, messenger)?,
+                PremiumCard::Count => send_card_played_message(action, game, messenger)?,
+                PremiumCard::Prince => prince_action(action, game, messenger)?,
+                PremiumCard::Constable => send_card_played_message(action, game, messenger)?,
+                PremiumCard::King => king_action(action, game, messenger)?,
+                PremiumCard::DowagerQueen => dowager_queen_action(action, game, messenger)?,
+                PremiumCard::Countess => countess_action(action, game, messenger)?,
+                PremiumCard::Princess => princess_action(action, game, messenger)?,
+                PremiumCard::Bishop => bishop_action(action, game, messenger)?,
+            }
+        }
+    }
+
+    /// A card that chooses players must choose the player picked by a
+    /// Sycophant, unless that player can't be chosen.
+    #[throws]
+    fn check_action(&self, action: &CardAction, game: &Game) {
+        if let Some(forced) = game.forced_target() {
+            let rules = self.rules_for_card(action.card())?;
+            let can_target = game.is_active(forced)
+                && !game.is_protected(forced)
+                && (rules.current_allowed_as_target() || forced != action.current());
+            if rules.target_required() && can_target {
+                let targeted = action.target().ok() == Some(forced)
+                    || action.second_target().ok() == Some(forced);
+                if !targeted {
+                    throw!(Error::BadActionMustTarget(forced));
+                }
+            }
+        }
+    }
+
+    /// A player knocked out with the Constable in their discard pile gains
+    /// a token.
+    #[throws]
+    fn player_eliminated(&self, game: &mut Game, player_id: PlayerId) {
+        let has_constable = game
+            .player(player_id)?
+            .discards()
+            .iter()
+            .any(|c| PremiumCard::Constable.is_same_card(c.as_ref()));
+        if has_constable {
+            game.award_bonus_token(player_id)?;
+        }
+    }
+
+    /// A player who chose the winner with a Jester gains a token.
+    #[throws]
+    fn round_end_bonus_tokens(&self, game: &Game, outcome: &RoundOutcome) -> Vec<PlayerId> {
+        game.jesters()
+            .iter()
+            .filter(|(_, chosen)| outcome.winners().contains(chosen))
+            .map(|(player, _)| *player)
+            .collect()
+    }
+
+    /// Each Count in a player's discard pile adds 1 to their hand.
+    #[throws]
+    fn final_hand_value(&self, player: &Player) -> u32 {
+        let counts = player
+            .discards()
+            .iter()
+            .filter(|c| PremiumCard::Count.is_same_card(c.as_ref()))
+            .count();
+        u32::from(player.card_in_hand()?.value()) + counts as u32
+    }
+
+    /// When the deck runs out, the Princess beats the Bishop, so a player
+    /// holding the Bishop can't win if another player holds the Princess.
+    #[throws]
+    fn round_outcome(&self, game: &Game) -> Option<RoundOutcome> {
+        if game.active.len() <= 1 {
+            Some(RoundOutcome::new(
+                game.active.iter().copied().collect(),
+                RoundEndReason::LastPlayerStanding,
+            ))
+        } else if game.is_deck_empty() {
+            let holds = |player_id: PlayerId, card: PremiumCard| {
+                matches!(
+                    game.player(player_id).and_then(|p| p.card_in_hand()),
+                    Ok(c) if card.is_same_card(c)
+                )
+            };
+            let active = game
+                .player_ids()
+                .filter(|id| game.is_active(*id))
+                .collect::<Vec<_>>();
+            let princess = active.iter().any(|id| holds(*id, PremiumCard::Princess));
+            let contenders = active
+                .into_iter()
+                .filter(|id| !(princess && holds(*id, PremiumCard::Bishop)));
+            let winners = highest_hands(self, game, contenders)?;
+            Some(RoundOutcome::new(winners, RoundEndReason::DeckEmpty))
+        } else {
+            None
+        }
+    }
+}
+
+#[throws]
+fn premium_card(card: &dyn Card) -> PremiumCard {
+    if let Some(premium) = PremiumCard::from_card(card) {
+        premium
+    } else {
+        throw!(Error::BadActionUnknownCard(card.name().to_string()));
+    }
+}
+
+#[throws]
+fn send_card_played_with_targets_message(
+    action: &CardAction,
+    game: &Game,
+    messenger: &mut dyn Messenger,
+) {
+    if action.has_second_target() {
+        messenger.event(
+            game,
+            &GameEvent::CardPlayed {
+                player: action.current(),
+                card: action.boxed_card(),
+                targets: vec![action.target()?, action.second_target()?],
+            },
+        );
+    } else {
+        send_card_played_with_target_message(action, game, messenger)?;
+    }
+}
+
+#[throws]
+fn jester_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    send_card_played_with_target_message(action, game, messenger)?;
+    game.add_jester(action.current(), action.target()?)?;
+}
+
+#[throws]
+fn guard_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    let current_id = action.current();
+    let target_id = action.target()?;
+    let guess = action.guess()?;
+
+    messenger.event(
+        game,
+        &GameEvent::GuessMade {
+            player: current_id,
+            card: action.boxed_card(),
+            target: target_id,
+            guess: action.boxed_guess()?,
+            by_value: true,
+        },
+    );
+
+    let target_card = game.hand_card(target_id)?;
+    if PremiumCard::Assassin.is_same_card(target_card.as_ref()) {
+        game.make_inactive(current_id)?;
+        game.discard_hand(target_id)?;
+        game.redraw(target_id)?;
+        messenger.event(
+            game,
+            &GameEvent::PlayerEliminated {
+                player: current_id,
+                reason: EliminationReason::Assassin(target_id),
+            },
+        );
+    } else if guess.has_same_value(target_card.as_ref()) {
+        game.make_inactive(target_id)?;
+        messenger.event(
+            game,
+            &GameEvent::PlayerEliminated {
+                player: target_id,
+                reason: EliminationReason::CardGuessed(target_card),
+            },
+        );
+    } else {
+        messenger.event(
+            game,
+            &GameEvent::GuessMissed {
+                target: target_id,
+                guess: action.boxed_guess()?,
+                by_value: true,
+            },
+        );
+    }
+}
+
+#[throws]
+fn cardinal_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    send_card_played_with_targets_message(action, game, messenger)?;
+
+    let current_id = action.current();
+    let first = action.target()?;
+    let second = action.second_target()?;
+    game.swap_hands(first, second)?;
+    messenger.event(game, &GameEvent::HandsSwapped { first, second });
+
+    // The current player looks at the hand of a player other than themselves.
+    // Swapping doesn't depend on the order of the targets, so when neither
+    // target is the current player, the first target is the one they chose
+    // to look at.
+    let looked_at = if first == current_id { second } else { first };
+    messenger.event(
+        game,
+        &GameEvent::CardLookedAt {
+            owner: looked_at,
+            viewer: current_id,
+            card: game.hand_card(looked_at)?,
+        },
+    );
+    game.reveal_hand(current_id, looked_at)?;
+}
+
+#[throws]
+fn baroness_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    send_card_played_with_targets_message(action, game, messenger)?;
+
+    let current_id = action.current();
+    let mut targets = vec![action.target()?];
+    if action.has_second_target() {
+        targets.push(action.second_target()?);
+    }
+    for target_id in targets {
+        messenger.event(
+            game,
+            &GameEvent::HandShown {
+                owner: target_id,
+                viewer: current_id,
+                card: action.boxed_card(),
+            },
+        );
+        messenger.event(
+            game,
+            &GameEvent::CardRevealed {
+                owner: target_id,
+                viewer: current_id,
+                card: game.hand_card(target_id)?,
+            },
+        );
+        game.reveal_hand(current_id, target_id)?;
+    }
+}
+
+#[throws]
+fn sycophant_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    send_card_played_with_target_message(action, game, messenger)?;
+    let target_id = action.target()?;
+    game.set_forced_target(target_id)?;
+    messenger.event(game, &GameEvent::ForcedTargetSet { target: target_id });
+}
+
+#[throws]
+fn dowager_queen_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    send_card_played_with_target_message(action, game, messenger)?;
+
+    let current_id = action.current();
+    let target_id = action.target()?;
+    let player_card = game.hand_card(current_id)?;
+    let target_card = game.hand_card(target_id)?;
+    // The players show each other their hands.
+    game.reveal_hand(current_id, target_id)?;
+    game.reveal_hand(target_id, current_id)?;
+
+    let loser = if player_card.has_same_value(target_card.as_ref()) {
+        None
+    } else if player_card.value() > target_card.value() {
+        Some((current_id, player_card))
+    } else {
+        Some((target_id, target_card))
+    };
+    messenger.event(
+        game,
+        &GameEvent::CardsCompared {
+            player: current_id,
+            card: action.boxed_card(),
+            target: target_id,
+            loser: loser.as_ref().map(|(id, _)| *id),
+        },
+    );
+    if let Some((out_id, out_card)) = loser {
+        game.make_inactive(out_id)?;
+        messenger.event(
+            game,
+            &GameEvent::PlayerEliminated {
+                player: out_id,
+                reason: EliminationReason::LostComparison(out_card),
+            },
+        );
+    }
+}
+
+/// The target does not get the option to discard and redraw after a correct
+/// guess; they keep their hand.
+#[throws]
+fn bishop_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    let current_id = action.current();
+    let target_id = action.target()?;
+    let guess = action.guess()?;
+
+    messenger.event(
+        game,
+        &GameEvent::GuessMade {
+            player: current_id,
+            card: action.boxed_card(),
+            target: target_id,
+            guess: action.boxed_guess()?,
+            by_value: true,
+        },
+    );
+
+    if guess.has_same_value(game.player(target_id)?.card_in_hand()?) {
+        game.award_bonus_token(current_id)?;
+        messenger.event(
+            game,
+            &GameEvent::GuessRewarded {
+                player: current_id,
+                target: target_id,
+                guess: action.boxed_guess()?,
+                by_value: true,
+            },
+        );
+    } else {
+        messenger.event(
+            game,
+            &GameEvent::GuessMissed {
+                target: target_id,
+                guess: action.boxed_guess()?,
+                by_value: true,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::test::deck;
+    use crate::game::GameBuilder;
+    use crate::messenger::test::TestMessenger;
+    use std::rc::Rc;
+    use PremiumCard::*;
+
+    /// Deals one card to each of five players, sets one aside, then deals
+    /// the rest in order as players draw.
+    fn five_player_game(hands: [PremiumCard; 5], draws: &[PremiumCard]) -> Game {
+        let mut cards = hands.to_vec();
+        cards.push(Countess);
+        cards.extend_from_slice(draws);
+        cards.extend_from_slice(&[Guard; 8]);
+        Game::with_deck(Rc::new(PremiumRuleset), 5, PlayerId(0), deck(&cards)).unwrap()
+    }
+
+    fn hand_of(game: &Game, player_index: usize) -> Vec<PremiumCard> {
+        game.player(PlayerId(player_index))
+            .unwrap()
+            .hand()
+            .iter()
+            .map(|c| PremiumCard::from_card(c.as_ref()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    #[throws]
+    fn test_player_counts() {
+        let builder = || GameBuilder::new().ruleset(PremiumRuleset);
+        assert_eq!(
+            Some(Error::InvalidNumberOfPlayers(4)),
+            builder().num_players(4).build().err()
+        );
+        assert_eq!(3, builder().num_players(8).build_match()?.tokens_to_win());
+    }
+
+    #[test]
+    #[throws]
+    fn test_guard_guesses_number() {
+        let mut game = five_player_game([Guard, Cardinal, Baron, Baron, Baron], &[Priest]);
+        let mut messenger = TestMessenger::new();
+
+        assert_eq!(
+            Some(Error::BadActionInvalidGuess("Guard".to_string())),
+            game.perform_action(
+                CardAction::new(Guard, PlayerId(0), Some(PlayerId(1)), Some(Guard)),
+                &mut messenger
+            )
+            .err()
+        );
+        // A Priest and a Cardinal are both 2s.
+        game.perform_action(
+            CardAction::new(Guard, PlayerId(0), Some(PlayerId(1)), Some(Priest)),
+            &mut messenger,
+        )?;
+        assert!(!game.is_active(PlayerId(1)));
+    }
+
+    #[test]
+    #[throws]
+    fn test_assassin() {
+        let mut game = five_player_game(
+            [Guard, Assassin, Baron, Baron, Baron],
+            &[Priest, King, Handmaid],
+        );
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Guard, PlayerId(0), Some(PlayerId(1)), Some(Priest)),
+            &mut messenger,
+        )?;
+        assert!(!game.is_active(PlayerId(0)));
+        assert!(game.is_active(PlayerId(1)));
+        assert!(Assassin.is_same_card(game.player(PlayerId(1))?.discards()[0].as_ref()));
+        assert_eq!(vec![King, Handmaid], hand_of(&game, 1));
+    }
+
+    #[test]
+    #[throws]
+    fn test_cardinal() {
+        let mut game = five_player_game([Cardinal, Priest, Baron, King, Baron], &[Handmaid]);
+        let mut messenger = TestMessenger::new();
+
+        assert_eq!(
+            Some(Error::BadActionMissingSecondTarget),
+            game.perform_action(
+                CardAction::new(Cardinal, PlayerId(0), Some(PlayerId(1)), None),
+                &mut messenger
+            )
+            .err()
+        );
+        game.perform_action(
+            CardAction::new(Cardinal, PlayerId(0), Some(PlayerId(1)), None)
+                .with_second_target(PlayerId(3)),
+            &mut messenger,
+        )?;
+        assert_eq!(King, hand_of(&game, 1)[0]);
+        assert_eq!(vec![Priest], hand_of(&game, 3));
+        assert!(messenger
+            .messages
+            .contains(&"Player 1: Player 2 now has a King".to_string()));
+
+        // Naming player 4 first looks at their new hand instead.
+        let mut game = five_player_game([Cardinal, Priest, Baron, King, Baron], &[Handmaid]);
+        let mut messenger = TestMessenger::new();
+        game.perform_action(
+            CardAction::new(Cardinal, PlayerId(0), Some(PlayerId(3)), None)
+                .with_second_target(PlayerId(1)),
+            &mut messenger,
+        )?;
+        assert_eq!(King, hand_of(&game, 1)[0]);
+        assert!(messenger
+            .messages
+            .contains(&"Player 1: Player 4 now has a Priest".to_string()));
+        assert!(!messenger
+            .messages
+            .contains(&"Player 1: Player 2 now has a King".to_string()));
+    }
+
+    #[test]
+    #[throws]
+    fn test_baroness() {
+        let mut game = five_player_game([Baroness, Priest, Baron, King, Baron], &[Handmaid]);
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Baroness, PlayerId(0), Some(PlayerId(1)), None)
+                .with_second_target(PlayerId(3)),
+            &mut messenger,
+        )?;
+        assert!(messenger
+            .messages
+            .contains(&"Player 1: Player 2 shows you a Priest".to_string()));
+        assert!(messenger
+            .messages
+            .contains(&"Player 1: Player 4 shows you a King".to_string()));
+    }
+
+    #[test]
+    #[throws]
+    fn test_sycophant() {
+        let mut game =
+            five_player_game([Sycophant, Guard, Baron, King, Baron], &[Handmaid, Priest]);
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Sycophant, PlayerId(0), Some(PlayerId(3)), None),
+            &mut messenger,
+        )?;
+        assert_eq!(Some(PlayerId(3)), game.forced_target());
+        assert_eq!(
+            Some(Error::BadActionMustTarget(PlayerId(3))),
+            game.perform_action(
+                CardAction::new(Guard, PlayerId(1), Some(PlayerId(2)), Some(Baron)),
+                &mut messenger
+            )
+            .err()
+        );
+        game.perform_action(
+            CardAction::new(Guard, PlayerId(1), Some(PlayerId(3)), Some(Baron)),
+            &mut messenger,
+        )?;
+        assert_eq!(None, game.forced_target());
+    }
+
+    #[test]
+    #[throws]
+    fn test_constable() {
+        let mut game =
+            five_player_game([Constable, Guard, Baron, Baron, Baron], &[Priest, Handmaid]);
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Constable, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        game.perform_action(
+            CardAction::new(Guard, PlayerId(1), Some(PlayerId(0)), Some(Priest)),
+            &mut messenger,
+        )?;
+        assert!(!game.is_active(PlayerId(0)));
+        assert_eq!(&[PlayerId(0)], game.bonus_tokens());
+    }
+
+    #[test]
+    #[throws]
+    fn test_dowager_queen() {
+        let mut game = five_player_game([DowagerQueen, Priest, Baron, King, Baron], &[Princess]);
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(DowagerQueen, PlayerId(0), Some(PlayerId(3)), None),
+            &mut messenger,
+        )?;
+        assert!(!game.is_active(PlayerId(0)));
+        assert!(game.is_active(PlayerId(3)));
+    }
+
+    #[test]
+    #[throws]
+    fn test_bishop() {
+        let mut game = five_player_game([Bishop, Priest, Baron, King, Baron], &[Handmaid]);
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Bishop, PlayerId(0), Some(PlayerId(3)), Some(King)),
+            &mut messenger,
+        )?;
+        assert!(game.is_active(PlayerId(3)));
+        assert_eq!(&[PlayerId(0)], game.bonus_tokens());
+    }
+
+    #[test]
+    #[throws]
+    fn test_jester_and_count() {
+        let hands = [Jester, Count, Baron, Priest, Baron];
+        let mut cards = hands.to_vec();
+        cards.extend_from_slice(&[Countess, Prince, Prince, Handmaid, Handmaid, Handmaid]);
+        let mut game = Game::with_deck(Rc::new(PremiumRuleset), 5, PlayerId(0), deck(&cards))?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Jester, PlayerId(0), Some(PlayerId(1)), None),
+            &mut messenger,
+        )?;
+        game.perform_action(
+            CardAction::new(Count, PlayerId(1), None, None),
+            &mut messenger,
+        )?;
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(2), None, None),
+            &mut messenger,
+        )?;
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(3), None, None),
+            &mut messenger,
+        )?;
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(4), None, None),
+            &mut messenger,
+        )?;
+
+        // Players 0 and 1 both hold a Prince, but player 1 discarded a Count.
+        let outcome = game.round_outcome().unwrap();
+        assert_eq!(&[PlayerId(1)], outcome.winners());
+        assert_eq!(&[PlayerId(0)], game.bonus_tokens());
+    }
+
+    #[test]
+    #[throws]
+    fn test_princess_beats_bishop() {
+        let showdown = |second: PremiumCard, discard: PremiumCard| {
+            // Player 0 holds the Bishop, and players 0 and 1 discard the same
+            // card before the deck runs out.
+            let mut game = Game::with_deck(
+                Rc::new(PremiumRuleset),
+                5,
+                PlayerId(0),
+                deck(&[
+                    Bishop, second, Baron, Baron, Baron, Countess, discard, discard, Guard, Guard,
+                    Guard,
+                ]),
+            )
+            .unwrap();
+            let mut messenger = TestMessenger::new();
+            for action in [
+                CardAction::new(discard, PlayerId(0), None, None),
+                CardAction::new(discard, PlayerId(1), None, None),
+                CardAction::new(Guard, PlayerId(2), Some(PlayerId(3)), Some(Priest)),
+                CardAction::new(Guard, PlayerId(3), Some(PlayerId(4)), Some(Priest)),
+                CardAction::new(Guard, PlayerId(4), Some(PlayerId(2)), Some(Priest)),
+            ] {
+                game.perform_action(action, &mut messenger).unwrap();
+            }
+            game.round_outcome().unwrap().winners().to_vec()
+        };
+
+        // The Princess wins, even against a Bishop worth 10 with a Count.
+        assert_eq!(vec![PlayerId(1)], showdown(Princess, Handmaid));
+        assert_eq!(vec![PlayerId(1)], showdown(Princess, Count));
+        assert_eq!(vec![PlayerId(0)], showdown(King, Handmaid));
+    }
+}
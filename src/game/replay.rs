@@ -0,0 +1,248 @@
+use crate::game::history::{GameAction, Silent};
+use crate::game::ruleset::Ruleset;
+use crate::messenger::Messenger;
+use crate::state::PlayerId;
+use crate::{Error, Game};
+use fehler::{throw, throws};
+use std::rc::Rc;
+
+/// A game recorded as its seed, its players, and every action taken, so
+/// that it can be stepped through forward and backward.
+#[derive(Debug)]
+pub struct Replay {
+    ruleset: Rc<dyn Ruleset>,
+    names: Vec<String>,
+    first_player: PlayerId,
+    seed: u64,
+    actions: Vec<GameAction>,
+
+    /// The game after the first `position` actions.
+    game: Game,
+    position: usize,
+}
+
+impl Replay {
+    /// Creates a replay, checking every action against the rules. The
+    /// replay starts before the first action.
+    #[throws]
+    pub fn new<S: AsRef<str>>(
+        ruleset: impl Ruleset + 'static,
+        names: &[S],
+        first_player: PlayerId,
+        seed: u64,
+        actions: Vec<GameAction>,
+    ) -> Replay {
+        let names = names.iter().map(|n| n.as_ref().to_string()).collect();
+        Replay::with_ruleset(Rc::new(ruleset), names, first_player, seed, actions)?
+    }
+
+    #[throws]
+    pub(crate) fn with_ruleset(
+        ruleset: Rc<dyn Ruleset>,
+        names: Vec<String>,
+        first_player: PlayerId,
+        seed: u64,
+        actions: Vec<GameAction>,
+    ) -> Replay {
+        let game = Game::from_seed(Rc::clone(&ruleset), &names, first_player, seed)?;
+        let mut replay = Replay {
+            ruleset,
+            names,
+            first_player,
+            seed,
+            actions,
+            game,
+            position: 0,
+        };
+        replay.seek(replay.len())?;
+        replay.seek(0)?;
+        replay
+    }
+
+    pub fn ruleset(&self) -> &dyn Ruleset {
+        self.ruleset.as_ref()
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn first_player(&self) -> PlayerId {
+        self.first_player
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn actions(&self) -> &[GameAction] {
+        &self.actions
+    }
+
+    /// The number of actions in the replay.
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// The number of actions that have been played.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The game as it stands after the actions played so far.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Plays the next action. Returns false if every action has been played.
+    #[throws]
+    pub fn step_forward(&mut self, messenger: &mut impl Messenger) -> bool {
+        if let Some(action) = self.actions.get(self.position) {
+            if let Err(error) = self.game.take_action(action.clone(), messenger) {
+                throw!(Error::BadReplayAction(self.position, error.to_string()));
+            }
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Takes back the last action played. Returns false if no action has
+    /// been played.
+    #[throws]
+    pub fn step_back(&mut self) -> bool {
+        if self.position == 0 {
+            false
+        } else {
+            self.game.undo()?;
+            self.position -= 1;
+            true
+        }
+    }
+
+    /// Moves to the game after the first `position` actions, without any
+    /// messages.
+    #[throws]
+    pub fn seek(&mut self, position: usize) {
+        if position > self.len() {
+            throw!(Error::InvalidReplayPosition(position));
+        }
+        if position < self.position {
+            self.game = Game::from_seed(
+                Rc::clone(&self.ruleset),
+                &self.names,
+                self.first_player,
+                self.seed,
+            )?;
+            self.position = 0;
+        }
+        while self.position < position {
+            self.step_forward(&mut Silent)?;
+        }
+    }
+}
+
+impl Game {
+    /// Records the game so far as a replay, positioned after the last action.
+    #[throws]
+    pub fn replay(&self) -> Replay {
+        let (seed, start) = match (self.seed(), self.history.start()) {
+            (Some(seed), Some(start)) => (seed, start),
+            _ => throw!(Error::NoSeed),
+        };
+        let mut replay = Replay::with_ruleset(
+            Rc::clone(&self.ruleset),
+            start.names.clone(),
+            start.first_player,
+            seed,
+            self.actions().cloned().collect(),
+        )?;
+        replay.seek(replay.len())?;
+        replay
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::{CardAction, GameBuilder, OriginalRuleset};
+    use crate::messenger::test::TestMessenger;
+    use crate::state::OriginalCard;
+
+    /// Plays the first card in the current player's hand at the next player.
+    #[throws]
+    fn play_first_card(game: &mut Game, messenger: &mut TestMessenger) {
+        let current = game.current_player();
+        let card = OriginalCard::from_card(game.player(current)?.hand()[0].as_ref()).unwrap();
+        let target = PlayerId((current.index() + 1) % 3);
+        let candidates = [
+            CardAction::new(card, current, Some(target), Some(OriginalCard::Priest)),
+            CardAction::new(card, current, Some(target), None),
+            CardAction::new(card, current, None, None),
+        ];
+        let action = candidates
+            .iter()
+            .find(|a| game.is_valid_action(a).is_ok())
+            .unwrap_or(&candidates[2]);
+        game.perform_action(action.clone(), messenger)?;
+    }
+
+    #[test]
+    #[throws]
+    fn test_step() {
+        let mut game = GameBuilder::new().num_players(3).seed(9).build()?;
+        let mut messenger = TestMessenger::new();
+        let mut states = vec![game.to_string()];
+        for _ in 0..3 {
+            if !game.is_round_over() {
+                play_first_card(&mut game, &mut messenger)?;
+                states.push(game.to_string());
+            }
+        }
+
+        let mut replay = game.replay()?;
+        assert_eq!(replay.len(), replay.position());
+        assert_eq!(game.seed(), replay.game().seed());
+        assert!(game.seed().is_some());
+        assert_eq!(states.last().unwrap(), &replay.game().to_string());
+
+        while replay.step_back()? {
+            assert_eq!(states[replay.position()], replay.game().to_string());
+        }
+        let mut messenger = TestMessenger::new();
+        assert!(replay.step_forward(&mut messenger)?);
+        assert_eq!(states[1], replay.game().to_string());
+        assert!(!messenger.events.is_empty());
+
+        assert_eq!(
+            Some(Error::InvalidReplayPosition(replay.len() + 1)),
+            replay.seek(replay.len() + 1).err()
+        );
+    }
+
+    #[test]
+    fn test_invalid_action() {
+        // Player 2 can't act first.
+        let actions = vec![GameAction::Play(CardAction::new(
+            OriginalCard::Guard,
+            PlayerId(1),
+            Some(PlayerId(0)),
+            Some(OriginalCard::Priest),
+        ))];
+        let error = Replay::new(OriginalRuleset, &["Ann", "Bo"], PlayerId(0), 1, actions).err();
+        assert!(matches!(error, Some(Error::BadReplayAction(0, _))));
+
+        let game = Game::with_deck(
+            Rc::new(OriginalRuleset),
+            2,
+            PlayerId(0),
+            OriginalCard::deck(),
+        );
+        assert_eq!(Some(Error::NoSeed), game.unwrap().replay().err());
+    }
+}
@@ -0,0 +1,363 @@
+use crate::game::card_action::CardAction;
+use crate::game::card_rules::CardRules;
+use crate::game::game_event::GameEvent;
+use crate::game::original::{
+    baron_action, countess_action, guard_action, handmaid_action, king_action, priest_action,
+    prince_action, princess_action, send_card_played_message, send_no_target_message,
+};
+use crate::game::round_outcome::RoundOutcome;
+use crate::game::ruleset::Ruleset;
+use crate::messenger::Messenger;
+use crate::state::{Card, Deck, PlayerId, RevisedCard};
+use crate::{Error, Game};
+use fehler::{throw, throws};
+use std::ops::RangeInclusive;
+
+const NO_TARGET: CardRules = CardRules::new(false, false, false);
+const TARGET_REQUIRED: CardRules = CardRules::new(true, false, false);
+const GUESS_REQUIRED: CardRules = CardRules::new(true, false, true).forbid_guess("Guard");
+const TARGET_SELF_ALLOWED: CardRules = CardRules::new(true, true, false);
+
+const PRINCE: CardRules = TARGET_SELF_ALLOWED.must_play_instead("Countess");
+const KING: CardRules = TARGET_REQUIRED.must_play_instead("Countess");
+
+/// The number of cards that the Chancellor draws, and then returns.
+const CHANCELLOR_DRAWS: usize = 2;
+
+/// The 2019 revised edition of Love Letter, with the 21-card deck, for 2
+/// to 6 players.
+#[derive(Debug)]
+pub struct RevisedRuleset;
+
+impl Ruleset for RevisedRuleset {
+    fn id(&self) -> &'static str {
+        "revised"
+    }
+
+    fn deck(&self) -> Deck {
+        RevisedCard::deck()
+    }
+
+    fn player_counts(&self) -> RangeInclusive<usize> {
+        2..=6
+    }
+
+    fn tokens_to_win(&self, num_players: usize) -> usize {
+        match num_players {
+            2 => 6,
+            3 => 5,
+            4 => 4,
+            _ => 3,
+        }
+    }
+
+    #[throws]
+    fn rules_for_card(&self, card: &dyn Card) -> &CardRules {
+        match revised_card(card)? {
+            RevisedCard::Spy => &NO_TARGET,
+            RevisedCard::Guard => &GUESS_REQUIRED,
+            RevisedCard::Priest => &TARGET_REQUIRED,
+            RevisedCard::Baron => &TARGET_REQUIRED,
+            RevisedCard::Handmaid => &NO_TARGET,
+            RevisedCard::Prince => &PRINCE,
+            RevisedCard::Chancellor => &NO_TARGET,
+            RevisedCard::King => &KING,
+            RevisedCard::Countess => &NO_TARGET,
+            RevisedCard::Princess => &NO_TARGET,
+        }
+    }
+
+    #[throws]
+    fn perform_card_action(
+        &self,
+        action: &CardAction,
+        game: &mut Game,
+        messenger: &mut dyn Messenger,
+    ) {
+        if self.rules_for_card(action.card())?.target_required() && !action.has_target() {
+            send_no_target_message(action, game, messenger)?;
+        } else {
+            match revised_card(action.card())? {
+                RevisedCard::Spy => send_card_played_message(action, game, messenger)?,
+                RevisedCard::Guard => guard_action(action, game, messenger)?,
+                RevisedCard::Priest => priest_action(action, game, messenger)?,
+                RevisedCard::Baron => baron_action(action, game, messenger)?,
+                RevisedCard::Handmaid => handmaid_action(action, game, messenger)?,
+                RevisedCard::Prince => prince_action(action, game, messenger)?,
+                RevisedCard::Chancellor => chancellor_action(action, game, messenger)?,
+                RevisedCard::King => king_action(action, game, messenger)?,
+                RevisedCard::Countess => countess_action(action, game, messenger)?,
+                RevisedCard::Princess => princess_action(action, game, messenger)?,
+            }
+        }
+    }
+
+    /// If exactly one player still in the round played or discarded a Spy,
+    /// they gain a token.
+    #[throws]
+    fn round_end_bonus_tokens(&self, game: &Game, _outcome: &RoundOutcome) -> Vec<PlayerId> {
+        let mut spies = Vec::new();
+        for player_id in game.player_ids() {
+            let has_spy = game
+                .player(player_id)?
+                .discards()
+                .iter()
+                .any(|c| RevisedCard::Spy.is_same_card(c.as_ref()));
+            if game.is_active(player_id) && has_spy {
+                spies.push(player_id);
+            }
+        }
+
+        if spies.len() == 1 {
+            spies
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[throws]
+fn revised_card(card: &dyn Card) -> RevisedCard {
+    if let Some(revised) = RevisedCard::from_card(card) {
+        revised
+    } else {
+        throw!(Error::BadActionUnknownCard(card.name().to_string()));
+    }
+}
+
+/// The player draws two cards. Their turn doesn't end until they have put
+/// two cards back with `Game::return_cards`.
+#[throws]
+fn chancellor_action(action: &CardAction, game: &mut Game, messenger: &mut dyn Messenger) {
+    send_card_played_message(action, game, messenger)?;
+
+    game.draw_cards_to_return(CHANCELLOR_DRAWS)?;
+    messenger.event(
+        game,
+        &GameEvent::CardsDrawnToReturn {
+            player: action.current(),
+            card: action.boxed_card(),
+            count: game.cards_to_return(),
+        },
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::test::deck;
+    use crate::game::{GameBuilder, RoundEndReason};
+    use crate::messenger::test::TestMessenger;
+    use std::rc::Rc;
+    use RevisedCard::*;
+
+    fn revised() -> Rc<dyn Ruleset> {
+        Rc::new(RevisedRuleset)
+    }
+
+    fn hand_of(game: &Game, player_index: usize) -> Vec<RevisedCard> {
+        game.player(PlayerId(player_index))
+            .unwrap()
+            .hand()
+            .iter()
+            .map(|c| RevisedCard::from_card(c.as_ref()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    #[throws]
+    fn test_player_counts() {
+        let game = GameBuilder::new()
+            .num_players(6)
+            .ruleset(RevisedRuleset)
+            .build()?;
+        assert_eq!(21 - 6 - 1 - 1, game.table.deck().cards_remaining());
+
+        assert_eq!(
+            Some(Error::InvalidNumberOfPlayers(7)),
+            GameBuilder::new()
+                .num_players(7)
+                .ruleset(RevisedRuleset)
+                .build()
+                .err()
+        );
+        assert_eq!(6, RevisedRuleset.tokens_to_win(2));
+        assert_eq!(3, RevisedRuleset.tokens_to_win(6));
+    }
+
+    #[test]
+    #[throws]
+    fn test_chancellor() {
+        let mut game = Game::with_deck(
+            revised(),
+            3,
+            PlayerId(0),
+            deck(&[
+                Chancellor, Guard, Priest, Baron, Handmaid, Spy, King, Prince, Countess,
+            ]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Chancellor, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        assert_eq!(PlayerId(0), game.current_player());
+        assert_eq!(2, game.cards_to_return());
+        assert_eq!(vec![Handmaid, Spy, King], hand_of(&game, 0));
+
+        assert_eq!(
+            Some(Error::BadActionMustReturnCards(2)),
+            game.perform_action(
+                CardAction::new(Spy, PlayerId(0), None, None),
+                &mut messenger
+            )
+            .err()
+        );
+        assert_eq!(
+            Some(Error::BadActionMustReturnCards(2)),
+            game.return_cards(&[Handmaid], &mut messenger).err()
+        );
+        assert_eq!(
+            Some(Error::BadActionPlayerDoesntHaveCard(
+                PlayerId(0),
+                "Spy".to_string()
+            )),
+            game.return_cards(&[Spy, Spy], &mut messenger).err()
+        );
+        assert_eq!(vec![Handmaid, Spy, King], hand_of(&game, 0));
+
+        game.return_cards(&[King, Handmaid], &mut messenger)?;
+        assert_eq!(0, game.cards_to_return());
+        assert_eq!(vec![Spy], hand_of(&game, 0));
+
+        // Player 1 draws the Prince, leaving the Countess on top of the two
+        // returned cards.
+        assert_eq!(PlayerId(1), game.current_player());
+        assert_eq!(vec![Guard, Prince], hand_of(&game, 1));
+        let mut remaining = vec![];
+        while let Some(card) = game.table.deck_mut().deal_one() {
+            remaining.push(RevisedCard::from_card(card.as_ref()).unwrap());
+        }
+        assert_eq!(vec![Countess, King, Handmaid], remaining);
+    }
+
+    #[test]
+    #[throws]
+    fn test_chancellor_near_empty_deck() {
+        let mut game = Game::with_deck(
+            revised(),
+            3,
+            PlayerId(0),
+            deck(&[Chancellor, Guard, Priest, Baron, Handmaid, Spy]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        // Only one card is left, so only one card is drawn and returned.
+        game.perform_action(
+            CardAction::new(Chancellor, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        assert_eq!(1, game.cards_to_return());
+        game.return_cards(&[Handmaid], &mut messenger)?;
+        assert_eq!(vec![Guard, Handmaid], hand_of(&game, 1));
+        assert_eq!(
+            Some(Error::BadActionNoCardsToReturn),
+            game.return_cards(&[Guard], &mut messenger).err()
+        );
+
+        // With an empty deck, the Chancellor has no effect.
+        let mut game = Game::with_deck(
+            revised(),
+            3,
+            PlayerId(0),
+            deck(&[Chancellor, Guard, Priest, Baron, Handmaid]),
+        )?;
+        game.perform_action(
+            CardAction::new(Chancellor, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        assert_eq!(0, game.cards_to_return());
+        assert!(game.is_round_over());
+    }
+
+    #[test]
+    #[throws]
+    fn test_spy_bonus() {
+        let mut game = Game::with_deck(
+            revised(),
+            3,
+            PlayerId(0),
+            deck(&[Spy, Guard, King, Countess, Priest, Handmaid, Handmaid]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Spy, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(1), None, None),
+            &mut messenger,
+        )?;
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(2), None, None),
+            &mut messenger,
+        )?;
+
+        assert_eq!(
+            Some(&RoundOutcome::new(
+                vec![PlayerId(2)],
+                RoundEndReason::DeckEmpty
+            )),
+            game.round_outcome()
+        );
+        assert_eq!(&[PlayerId(0)], game.bonus_tokens());
+    }
+
+    #[test]
+    #[throws]
+    fn test_two_spies_earn_nothing() {
+        let mut game = Game::with_deck(
+            revised(),
+            3,
+            PlayerId(0),
+            deck(&[Spy, Spy, King, Countess, Priest, Handmaid, Handmaid]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Spy, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        game.perform_action(
+            CardAction::new(Spy, PlayerId(1), None, None),
+            &mut messenger,
+        )?;
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(2), None, None),
+            &mut messenger,
+        )?;
+
+        assert!(game.is_round_over());
+        assert!(game.bonus_tokens().is_empty());
+    }
+
+    #[test]
+    #[throws]
+    fn test_prince_forces_princess_discard() {
+        let mut game = Game::with_deck(
+            revised(),
+            3,
+            PlayerId(0),
+            deck(&[Prince, Princess, Guard, Countess, Handmaid, Priest]),
+        )?;
+        let mut messenger = TestMessenger::new();
+
+        game.perform_action(
+            CardAction::new(Prince, PlayerId(0), Some(PlayerId(1)), None),
+            &mut messenger,
+        )?;
+        assert!(!game.is_active(PlayerId(1)));
+    }
+}
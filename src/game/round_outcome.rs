@@ -0,0 +1,60 @@
+use crate::state::PlayerId;
+use std::fmt::{Display, Formatter};
+
+/// Why a round came to an end.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RoundEndReason {
+    /// Every other player was eliminated.
+    LastPlayerStanding,
+    /// The deck ran out, and the remaining hands were compared.
+    DeckEmpty,
+}
+
+/// The result of a finished round.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundOutcome {
+    winners: Vec<PlayerId>,
+    reason: RoundEndReason,
+}
+
+impl RoundOutcome {
+    pub fn new(winners: Vec<PlayerId>, reason: RoundEndReason) -> RoundOutcome {
+        RoundOutcome { winners, reason }
+    }
+
+    /// The players who won the round.
+    ///
+    /// There is usually only one winner, but a round that ends with an
+    /// empty deck can end in a tie.
+    pub fn winners(&self) -> &[PlayerId] {
+        &self.winners
+    }
+
+    pub fn reason(&self) -> RoundEndReason {
+        self.reason
+    }
+
+    /// Describes the outcome, using `name_of` to name the winners.
+    pub fn describe(&self, name_of: impl Fn(PlayerId) -> String) -> String {
+        let winners = self
+            .winners
+            .iter()
+            .map(|w| name_of(*w))
+            .collect::<Vec<_>>()
+            .join(" and ");
+        match self.reason {
+            RoundEndReason::LastPlayerStanding => {
+                format!("{} wins the round as the last player standing.", winners)
+            }
+            RoundEndReason::DeckEmpty => format!("The deck is empty. {} wins the round.", winners),
+        }
+    }
+}
+
+impl Display for RoundOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.describe(|id| id.to_string()))
+    }
+}
@@ -0,0 +1,153 @@
+use crate::game::card_action::CardAction;
+use crate::game::card_rules::CardRules;
+use crate::game::round_outcome::{RoundEndReason, RoundOutcome};
+use crate::game::{OriginalRuleset, PremiumRuleset, RevisedRuleset};
+use crate::messenger::Messenger;
+use crate::state::{Card, Deck, Player, PlayerId};
+use crate::{Error, Game};
+use fehler::throws;
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+/// An edition, or variant, of Love Letter.
+///
+/// The Ruleset decides which cards are in the deck, how many players may
+/// play, what each card does, and how rounds and matches are won. Game
+/// knows only about turns; everything else is delegated to the Ruleset.
+pub trait Ruleset: Debug {
+    /// A short, unique name for the ruleset.
+    fn id(&self) -> &'static str;
+
+    /// Builds a complete, unshuffled deck.
+    fn deck(&self) -> Deck;
+
+    /// The numbers of players that may play with this ruleset.
+    fn player_counts(&self) -> RangeInclusive<usize>;
+
+    /// The number of cards removed face up at the start of each round.
+    fn burned_card_count(&self, num_players: usize) -> usize {
+        if num_players == 2 {
+            3
+        } else {
+            0
+        }
+    }
+
+    /// The number of tokens of affection needed to win a match.
+    fn tokens_to_win(&self, num_players: usize) -> usize;
+
+    /// The rules used to validate an action that plays `card`.
+    fn rules_for_card(&self, card: &dyn Card) -> Result<&CardRules, Error>;
+
+    /// Applies the effect of the card played in `action`. The card has
+    /// already been validated and discarded.
+    ///
+    /// Effects are applied with Game's public mutators, like
+    /// `make_inactive`, `make_protected`, `swap_hands` and `redraw`, and
+    /// reported by sending a GameEvent for each change.
+    fn perform_card_action(
+        &self,
+        action: &CardAction,
+        game: &mut Game,
+        messenger: &mut dyn Messenger,
+    ) -> Result<(), Error>;
+
+    /// Checks any rule that depends on the state of the round, rather than
+    /// on the card alone. This is called after the card's CardRules have
+    /// accepted the action.
+    #[throws]
+    fn check_action(&self, _action: &CardAction, _game: &Game) {}
+
+    /// Called when a player is knocked out of the round, before their hand
+    /// is discarded.
+    #[throws]
+    fn player_eliminated(&self, _game: &mut Game, _player_id: PlayerId) {}
+
+    /// The players who receive a bonus token of affection at the end of the
+    /// round, in addition to the round's winners.
+    #[throws]
+    fn round_end_bonus_tokens(&self, _game: &Game, _outcome: &RoundOutcome) -> Vec<PlayerId> {
+        Vec::new()
+    }
+
+    /// The value of a player's hand when hands are compared at the end of
+    /// a round.
+    #[throws]
+    fn final_hand_value(&self, player: &Player) -> u32 {
+        u32::from(player.card_in_hand()?.value())
+    }
+
+    /// Checks, at the end of a turn, whether the round is over.
+    ///
+    /// By default, the round ends when only one active player remains, or
+    /// when the deck runs out. In the latter case, the player with the
+    /// highest card wins, and ties are broken by the total value of each
+    /// player's discards.
+    #[throws]
+    fn round_outcome(&self, game: &Game) -> Option<RoundOutcome> {
+        if game.active.len() <= 1 {
+            Some(RoundOutcome::new(
+                game.active.iter().copied().collect(),
+                RoundEndReason::LastPlayerStanding,
+            ))
+        } else if game.is_deck_empty() {
+            let active = game.player_ids().filter(|id| game.is_active(*id));
+            let winners = highest_hands(self, game, active)?;
+            Some(RoundOutcome::new(winners, RoundEndReason::DeckEmpty))
+        } else {
+            None
+        }
+    }
+}
+
+/// The players, of `contenders`, with the highest `final_hand_value`. Ties
+/// are broken by the total value of each player's discards.
+#[throws]
+pub(crate) fn highest_hands<R: Ruleset + ?Sized>(
+    ruleset: &R,
+    game: &Game,
+    contenders: impl Iterator<Item = PlayerId>,
+) -> Vec<PlayerId> {
+    let mut scores = Vec::new();
+    for player_id in contenders {
+        let player = game.player(player_id)?;
+        let hand_value = ruleset.final_hand_value(player)?;
+        let discard_value = player
+            .discards()
+            .iter()
+            .map(|c| u32::from(c.value()))
+            .sum::<u32>();
+        scores.push((player_id, (hand_value, discard_value)));
+    }
+    let best = scores.iter().map(|(_, score)| *score).max();
+    scores
+        .iter()
+        .filter(|(_, score)| Some(*score) == best)
+        .map(|(player_id, _)| *player_id)
+        .collect()
+}
+
+/// Returns the ruleset from this crate with the given id, or None if there
+/// isn't one.
+pub fn builtin_ruleset(id: &str) -> Option<Rc<dyn Ruleset>> {
+    let rulesets: [Rc<dyn Ruleset>; 3] = [
+        Rc::new(OriginalRuleset),
+        Rc::new(PremiumRuleset),
+        Rc::new(RevisedRuleset),
+    ];
+    rulesets.iter().find(|r| r.id() == id).cloned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_builtin_ruleset() {
+        for id in &["original", "premium", "revised"] {
+            assert_eq!(Some(*id), builtin_ruleset(id).map(|r| r.id()));
+        }
+        assert!(builtin_ruleset("house-rules").is_none());
+    }
+}
@@ -0,0 +1,613 @@
+use crate::game::card_action::CardAction;
+use crate::game::history::{GameAction, History, LogEntry, Start, UndoPolicy};
+use crate::game::replay::Replay;
+use crate::game::ruleset::{builtin_ruleset, Ruleset};
+use crate::game::RoundOutcome;
+use crate::state::{Card, CardId, CardRegistry, Deck, IdentifiedCard, Player, PlayerId, Table};
+use crate::{Error, Game};
+use fehler::{throw, throws};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::rc::Rc;
+
+/// A card, recorded by name and value so that it can be found in a
+/// CardRegistry when the game is loaded.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SavedCard {
+    name: String,
+    value: u8,
+    id: Option<CardId>,
+}
+
+impl SavedCard {
+    fn new(card: &dyn Card) -> SavedCard {
+        SavedCard {
+            name: card.name().to_string(),
+            value: card.value(),
+            id: card.id(),
+        }
+    }
+
+    #[throws]
+    fn restore(&self, cards: &CardRegistry) -> Box<dyn Card> {
+        let card = if let Some(card) = cards.find(&self.name, self.value) {
+            card
+        } else {
+            throw!(Error::UnknownCard(self.name.clone(), self.value));
+        };
+        if let Some(id) = self.id {
+            Box::new(IdentifiedCard::new(id, card))
+        } else {
+            card
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SavedPlayer {
+    name: String,
+    hand: Vec<SavedCard>,
+    discards: Vec<SavedCard>,
+}
+
+/// Everything needed to recreate a Game, in a form that serde can save.
+///
+/// Cards are saved by name and value, and the ruleset by its id. Both are
+/// looked up again when the game is restored.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SavedGame {
+    ruleset: String,
+    players: Vec<SavedPlayer>,
+    /// The cards in the deck, in the order that they will be dealt.
+    deck: Vec<SavedCard>,
+    out_card: Option<SavedCard>,
+    burned: Vec<SavedCard>,
+
+    current_player: PlayerId,
+    active: Vec<PlayerId>,
+    protected: Vec<PlayerId>,
+    forced_target: Option<PlayerId>,
+    jesters: Vec<(PlayerId, PlayerId)>,
+    bonus_tokens: Vec<PlayerId>,
+    cards_to_return: usize,
+    revealed: Vec<(PlayerId, PlayerId, SavedCard)>,
+    outcome: Option<RoundOutcome>,
+    /// None if the game can't be undone or replayed, like a game that was
+    /// itself restored from a save without a history.
+    history: Option<SavedHistory>,
+}
+
+/// How the round started, and every action since, so that a restored game
+/// can still be undone and replayed.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SavedHistory {
+    players: Vec<String>,
+    first_player: PlayerId,
+    /// The deck that the round was dealt from, in the order it was dealt.
+    deck: Vec<SavedCard>,
+    seed: Option<u64>,
+    /// Each action, and whether it revealed hidden information.
+    log: Vec<(SavedAction, bool)>,
+    undone: Vec<SavedAction>,
+    policy: UndoPolicy,
+}
+
+impl SavedHistory {
+    fn new(history: &History) -> Option<SavedHistory> {
+        let start = history.start.as_ref()?;
+        Some(SavedHistory {
+            players: start.names.clone(),
+            first_player: start.first_player,
+            deck: start
+                .deck
+                .peek(start.deck.cards_remaining())
+                .into_iter()
+                .map(SavedCard::new)
+                .collect(),
+            seed: history.seed,
+            log: history
+                .log
+                .iter()
+                .map(|entry| (SavedAction::new(&entry.action), entry.revealed_hidden_info))
+                .collect(),
+            undone: history.undone.iter().map(SavedAction::new).collect(),
+            policy: history.policy,
+        })
+    }
+
+    #[throws]
+    fn restore(&self, cards: &CardRegistry) -> History {
+        let deck = self
+            .deck
+            .iter()
+            .map(|c| c.restore(cards))
+            .collect::<Result<_, _>>()?;
+        let mut log = Vec::new();
+        for (action, revealed_hidden_info) in &self.log {
+            log.push(LogEntry {
+                action: action.restore(cards)?,
+                revealed_hidden_info: *revealed_hidden_info,
+            });
+        }
+        History {
+            start: Some(Start {
+                names: self.players.clone(),
+                first_player: self.first_player,
+                deck: Deck::new(deck),
+            }),
+            seed: self.seed,
+            log,
+            undone: self
+                .undone
+                .iter()
+                .map(|a| a.restore(cards))
+                .collect::<Result<_, _>>()?,
+            policy: self.policy,
+        }
+    }
+}
+
+impl SavedGame {
+    /// Recreates the game with one of the rulesets and cards from this crate.
+    #[throws]
+    pub fn restore(&self) -> Game {
+        let ruleset = if let Some(ruleset) = builtin_ruleset(&self.ruleset) {
+            ruleset
+        } else {
+            throw!(Error::UnknownRuleset(self.ruleset.clone()));
+        };
+        self.restore_game(ruleset, &CardRegistry::with_builtin_cards())?
+    }
+
+    /// Recreates a game that was played with a ruleset, or cards, from
+    /// outside this crate. `ruleset` must have the same id as the ruleset
+    /// that was saved.
+    #[throws]
+    pub fn restore_with(&self, ruleset: impl Ruleset + 'static, cards: &CardRegistry) -> Game {
+        if ruleset.id() != self.ruleset {
+            throw!(Error::UnknownRuleset(self.ruleset.clone()));
+        }
+        self.restore_game(Rc::new(ruleset), cards)?
+    }
+
+    #[throws]
+    fn restore_game(&self, ruleset: Rc<dyn Ruleset>, cards: &CardRegistry) -> Game {
+        let restore_all = |saved: &[SavedCard]| -> Result<Vec<Box<dyn Card>>, Error> {
+            saved.iter().map(|c| c.restore(cards)).collect()
+        };
+
+        let names = self.players.iter().map(|p| &p.name).collect::<Vec<_>>();
+        if !ruleset.player_counts().contains(&names.len()) {
+            throw!(Error::InvalidNumberOfPlayers(names.len()));
+        }
+        let mut table = Table::with_names(&names)?;
+        for (player_id, saved) in table.player_ids().zip(&self.players).collect::<Vec<_>>() {
+            let player = table.player_mut(player_id)?;
+            for card in restore_all(&saved.hand)? {
+                player.add_card_to_hand(card);
+            }
+            for card in restore_all(&saved.discards)? {
+                player.add_card_to_discards(card);
+            }
+        }
+        table.set_deck(Deck::new(restore_all(&self.deck)?));
+        table.set_out_card(restore_all(self.out_card.as_slice())?.pop());
+        for card in restore_all(&self.burned)? {
+            table.burn_card(card);
+        }
+
+        let mut revealed = Vec::new();
+        for (viewer, owner, card) in &self.revealed {
+            revealed.push((*viewer, *owner, card.restore(cards)?));
+        }
+
+        let players = self
+            .active
+            .iter()
+            .chain(&self.protected)
+            .chain(&self.forced_target)
+            .chain(&self.bonus_tokens)
+            .chain(self.jesters.iter().flat_map(|(p, c)| vec![p, c]))
+            .chain(self.revealed.iter().flat_map(|(v, o, _)| vec![v, o]))
+            .chain(std::iter::once(&self.current_player));
+        for player_id in players {
+            table.player(*player_id)?;
+        }
+
+        let game = Game {
+            ruleset,
+            table,
+            #[cfg(test)]
+            stacked_deck: None,
+            current_player: self.current_player,
+            active: self.active.iter().copied().collect(),
+            protected: self.protected.iter().copied().collect(),
+            forced_target: self.forced_target,
+            jesters: self.jesters.clone(),
+            bonus_tokens: self.bonus_tokens.clone(),
+            cards_to_return: self.cards_to_return,
+            revealed,
+            outcome: self.outcome.clone(),
+            history: match &self.history {
+                Some(history) => history.restore(cards)?,
+                None => History::default(),
+            },
+        };
+        // The save must hold exactly the ruleset's cards.
+        game.audit()?;
+        game
+    }
+}
+
+impl Game {
+    /// Captures the complete state of the game, including the order of the
+    /// deck and every hidden card.
+    pub fn save(&self) -> SavedGame {
+        let save_all = |cards: &[Box<dyn Card>]| -> Vec<SavedCard> {
+            cards.iter().map(|c| SavedCard::new(c.as_ref())).collect()
+        };
+        let sorted = |players: &HashSet<PlayerId>| -> Vec<PlayerId> {
+            let mut players = players.iter().copied().collect::<Vec<_>>();
+            players.sort();
+            players
+        };
+
+        let deck = self.table.deck();
+        SavedGame {
+            ruleset: self.ruleset.id().to_string(),
+            players: self
+                .table
+                .player_ids()
+                .filter_map(|id| self.table.player(id).ok())
+                .map(|player: &Player| SavedPlayer {
+                    name: player.name().to_string(),
+                    hand: save_all(player.hand()),
+                    discards: save_all(player.discards()),
+                })
+                .collect(),
+            deck: deck
+                .peek(deck.cards_remaining())
+                .into_iter()
+                .map(SavedCard::new)
+                .collect(),
+            out_card: self.table.out_card().map(SavedCard::new),
+            burned: save_all(self.table.burned_cards()),
+            current_player: self.current_player,
+            active: sorted(&self.active),
+            protected: sorted(&self.protected),
+            forced_target: self.forced_target,
+            jesters: self.jesters.clone(),
+            bonus_tokens: self.bonus_tokens.clone(),
+            cards_to_return: self.cards_to_return,
+            revealed: self
+                .revealed
+                .iter()
+                .map(|(viewer, owner, card)| (*viewer, *owner, SavedCard::new(card.as_ref())))
+                .collect(),
+            outcome: self.outcome.clone(),
+            history: SavedHistory::new(&self.history),
+        }
+    }
+}
+
+/// An action, in a form that serde can save.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SavedAction {
+    Play {
+        card: SavedCard,
+        player: PlayerId,
+        target: Option<PlayerId>,
+        second_target: Option<PlayerId>,
+        guess: Option<SavedCard>,
+    },
+    ReturnCards(Vec<SavedCard>),
+}
+
+impl SavedAction {
+    fn new(action: &GameAction) -> SavedAction {
+        match action {
+            GameAction::Play(action) => SavedAction::Play {
+                card: SavedCard::new(action.card()),
+                player: action.current(),
+                target: action.target().ok(),
+                second_target: action.second_target().ok(),
+                guess: action.guess().ok().map(SavedCard::new),
+            },
+            GameAction::ReturnCards(cards) => {
+                SavedAction::ReturnCards(cards.iter().map(|c| SavedCard::new(c.as_ref())).collect())
+            }
+        }
+    }
+
+    #[throws]
+    fn restore(&self, cards: &CardRegistry) -> GameAction {
+        match self {
+            SavedAction::Play {
+                card,
+                player,
+                target,
+                second_target,
+                guess,
+            } => GameAction::Play(CardAction::from_parts(
+                card.restore(cards)?,
+                *player,
+                *target,
+                *second_target,
+                guess.as_ref().map(|g| g.restore(cards)).transpose()?,
+            )),
+            SavedAction::ReturnCards(saved) => GameAction::ReturnCards(
+                saved
+                    .iter()
+                    .map(|c| c.restore(cards))
+                    .collect::<Result<_, _>>()?,
+            ),
+        }
+    }
+}
+
+/// A Replay, in a form that serde can save. This is small enough to attach
+/// to a bug report, and replays the game exactly.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SavedReplay {
+    ruleset: String,
+    seed: u64,
+    players: Vec<String>,
+    first_player: PlayerId,
+    actions: Vec<SavedAction>,
+}
+
+impl SavedReplay {
+    /// Loads the replay with one of the rulesets and cards from this crate.
+    /// Every action is checked against the rules.
+    #[throws]
+    pub fn restore(&self) -> Replay {
+        let ruleset = if let Some(ruleset) = builtin_ruleset(&self.ruleset) {
+            ruleset
+        } else {
+            throw!(Error::UnknownRuleset(self.ruleset.clone()));
+        };
+        self.restore_replay(ruleset, &CardRegistry::with_builtin_cards())?
+    }
+
+    /// Loads a replay of a game played with a ruleset, or cards, from
+    /// outside this crate.
+    #[throws]
+    pub fn restore_with(&self, ruleset: impl Ruleset + 'static, cards: &CardRegistry) -> Replay {
+        if ruleset.id() != self.ruleset {
+            throw!(Error::UnknownRuleset(self.ruleset.clone()));
+        }
+        self.restore_replay(Rc::new(ruleset), cards)?
+    }
+
+    #[throws]
+    fn restore_replay(&self, ruleset: Rc<dyn Ruleset>, cards: &CardRegistry) -> Replay {
+        let actions = self
+            .actions
+            .iter()
+            .map(|a| a.restore(cards))
+            .collect::<Result<_, _>>()?;
+        Replay::with_ruleset(
+            ruleset,
+            self.players.clone(),
+            self.first_player,
+            self.seed,
+            actions,
+        )?
+    }
+}
+
+impl Replay {
+    pub fn save(&self) -> SavedReplay {
+        SavedReplay {
+            ruleset: self.ruleset().id().to_string(),
+            seed: self.seed(),
+            players: self.names().to_vec(),
+            first_player: self.first_player(),
+            actions: self.actions().iter().map(SavedAction::new).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::test::deck;
+    use crate::game::{CardAction, GameBuilder, OriginalRuleset, RevisedRuleset};
+    use crate::messenger::test::TestMessenger;
+    use crate::state::{CardLocation, RevisedCard};
+
+    #[throws]
+    fn round_trip(game: &Game) -> Game {
+        let json = serde_json::to_string(&game.save()).unwrap();
+        serde_json::from_str::<SavedGame>(&json)
+            .unwrap()
+            .restore()?
+    }
+
+    #[test]
+    #[throws]
+    fn test_round_trip() {
+        use RevisedCard::*;
+        // Restored games must hold every card, so stack the top of a
+        // complete deck.
+        let top = [
+            Handmaid, Guard, Priest, Baron, Chancellor, Spy, King, Prince, Countess,
+        ];
+        let mut rest = RevisedCard::deck();
+        let mut rest = rest
+            .deal(rest.cards_remaining())
+            .iter()
+            .map(|c| RevisedCard::from_card(c.as_ref()).unwrap())
+            .collect::<Vec<_>>();
+        for card in &top {
+            let index = rest.iter().position(|c| c == card).unwrap();
+            rest.remove(index);
+        }
+        let cards = top.iter().chain(&rest).copied().collect::<Vec<_>>();
+        let table = Table::with_names(&["Ann", "Bo", "Cy"])?;
+        let mut game = Game::with_table(Rc::new(RevisedRuleset), table, PlayerId(0), deck(&cards))?;
+        let mut messenger = TestMessenger::new();
+        game.perform_action(
+            CardAction::new(Handmaid, PlayerId(0), None, None),
+            &mut messenger,
+        )?;
+        game.perform_action(
+            CardAction::new(Guard, PlayerId(1), Some(PlayerId(2)), Some(Priest)),
+            &mut messenger,
+        )?;
+        assert!(!game.is_active(PlayerId(2)));
+
+        let mut restored = round_trip(&game)?;
+        assert_eq!(game.save(), restored.save());
+        assert_eq!(game.to_string(), restored.to_string());
+        assert_eq!("Ann", restored.name_of(PlayerId(0)));
+        assert!(!restored.is_active(PlayerId(2)));
+        assert_eq!(PlayerId(0), restored.current_player());
+        assert_eq!(Some(CardLocation::OutCard), restored.find_card(CardId(3)));
+
+        // Both games play on in exactly the same way.
+        for g in &mut [&mut game, &mut restored] {
+            g.perform_action(
+                CardAction::new(Chancellor, PlayerId(0), None, None),
+                &mut messenger,
+            )?;
+            g.return_cards(&[Prince, Countess], &mut messenger)?;
+        }
+        assert_eq!(game.save(), restored.save());
+    }
+
+    #[test]
+    #[throws]
+    fn test_round_trip_shuffled() {
+        let game = GameBuilder::new()
+            .player_names(&["Ann", "Bo"])
+            .seed(11)
+            .build()?;
+        let restored = round_trip(&game)?;
+        assert_eq!(game.save(), restored.save());
+        assert_eq!(game.burned_cards().len(), restored.burned_cards().len());
+        assert_eq!(
+            game.table.deck().to_string(),
+            restored.table.deck().to_string()
+        );
+    }
+
+    #[test]
+    #[throws]
+    fn test_round_trip_history() {
+        let mut game = GameBuilder::new().num_players(3).seed(8).build()?;
+        let mut messenger = TestMessenger::new();
+        for _ in 0..2 {
+            let action = game.legal_actions(game.current_player())?.remove(0);
+            game.perform_action(action, &mut messenger)?;
+        }
+        game.undo()?;
+
+        let mut restored = round_trip(&game)?;
+        assert_eq!(game.seed(), restored.seed());
+        assert_eq!(1, restored.actions().count());
+        assert!(restored.can_redo());
+        assert_eq!(game.replay()?.save(), restored.replay()?.save());
+
+        game.undo()?;
+        restored.undo()?;
+        assert_eq!(game.save(), restored.save());
+    }
+
+    #[test]
+    #[throws]
+    fn test_replay_round_trip() {
+        use RevisedCard::*;
+        let mut game = GameBuilder::new()
+            .player_names(&["Ann", "Bo"])
+            .ruleset(RevisedRuleset)
+            .seed(5)
+            .build()?;
+        let mut messenger = TestMessenger::new();
+        // Play whatever is legal until the round ends.
+        while !game.is_round_over() {
+            let current = game.current_player();
+            let other = PlayerId(1 - current.index());
+            let hand = game.player(current)?.hand().to_vec();
+            let played = hand.iter().find_map(|card| {
+                let card = RevisedCard::from_card(card.as_ref())?;
+                let target = if card == Prince {
+                    Some(current)
+                } else {
+                    Some(other)
+                };
+                [
+                    CardAction::new(card, current, target, Some(Priest)),
+                    CardAction::new(card, current, target, None),
+                    CardAction::new(card, current, None, None),
+                ]
+                .iter()
+                .find(|a| game.is_valid_action(a).is_ok())
+                .cloned()
+            });
+            game.perform_action(played.unwrap(), &mut messenger)?;
+            if game.cards_to_return() > 0 {
+                let hand = game.player(current)?.hand().to_vec();
+                let returned = hand[..game.cards_to_return()]
+                    .iter()
+                    .map(|c| RevisedCard::from_card(c.as_ref()).unwrap())
+                    .collect::<Vec<_>>();
+                game.return_cards(&returned, &mut messenger)?;
+            }
+        }
+
+        let json = serde_json::to_string(&game.replay()?.save()).unwrap();
+        let mut replay = serde_json::from_str::<SavedReplay>(&json)
+            .unwrap()
+            .restore()?;
+        assert_eq!(0, replay.position());
+        replay.seek(replay.len())?;
+        assert_eq!(game.save(), replay.game().save());
+    }
+
+    #[test]
+    #[throws]
+    fn test_unknown_ruleset_and_card() {
+        let mut saved = GameBuilder::new().build()?.save();
+        saved.ruleset = "house-rules".to_string();
+        assert_eq!(
+            Some(Error::UnknownRuleset("house-rules".to_string())),
+            saved.restore().err()
+        );
+
+        let saved = GameBuilder::new().build()?.save();
+        let error = saved
+            .restore_with(OriginalRuleset, &CardRegistry::new())
+            .err();
+        assert!(matches!(error, Some(Error::UnknownCard(_, _))));
+        assert_eq!(
+            Some(Error::UnknownRuleset("original".to_string())),
+            saved
+                .restore_with(RevisedRuleset, &CardRegistry::with_builtin_cards())
+                .err()
+        );
+
+        // A save with two Princesses doesn't hold the original edition.
+        let mut saved = GameBuilder::new().seed(3).build()?.save();
+        let card = saved
+            .deck
+            .iter_mut()
+            .find(|c| c.name != "Princess")
+            .unwrap();
+        card.name = "Princess".to_string();
+        card.value = 8;
+        assert!(matches!(
+            saved.restore(),
+            Err(Error::InternalErrorInvariantViolated(_))
+        ));
+
+        // The original edition is for at most 4 players.
+        let mut saved = GameBuilder::new().build()?.save();
+        saved.players.push(saved.players[0].clone());
+        saved.players[4].name = "Player 5".to_string();
+        assert_eq!(
+            Some(Error::InvalidNumberOfPlayers(5)),
+            saved.restore().err()
+        );
+    }
+}
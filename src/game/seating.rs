@@ -0,0 +1,155 @@
+use crate::state::{random_below, PlayerId};
+use crate::Error;
+use fehler::{throw, throws};
+use rand::RngCore;
+
+/// A player who is joining a game.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlayerProfile {
+    name: String,
+    days_since_date: Option<u32>,
+}
+
+impl PlayerProfile {
+    pub fn new(name: &str) -> PlayerProfile {
+        PlayerProfile {
+            name: name.to_string(),
+            days_since_date: None,
+        }
+    }
+
+    /// Records how many days ago the player last went on a date. Used by
+    /// `FirstPlayer::MostRecentDate`.
+    pub fn last_date(mut self, days_ago: u32) -> PlayerProfile {
+        self.days_since_date = Some(days_ago);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn days_since_date(&self) -> Option<u32> {
+        self.days_since_date
+    }
+}
+
+/// How to choose the player who goes first in the first round. In later
+/// rounds of a match, the winner of the previous round goes first.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FirstPlayer {
+    /// The player in the given seat.
+    Seat(PlayerId),
+    /// A player chosen at random.
+    Random,
+    /// The player who most recently went on a date, as the rulebook
+    /// suggests. Ties go to the player seated first, and if nobody has
+    /// been on a date, the first seat goes first.
+    MostRecentDate,
+}
+
+impl Default for FirstPlayer {
+    fn default() -> Self {
+        FirstPlayer::Seat(PlayerId(0))
+    }
+}
+
+impl FirstPlayer {
+    /// Chooses the first player from players who are already seated.
+    #[throws]
+    pub(crate) fn choose(&self, seated: &[PlayerProfile], rng: &mut dyn RngCore) -> PlayerId {
+        match self {
+            FirstPlayer::Seat(id) => {
+                if id.index() >= seated.len() {
+                    throw!(Error::InvalidPlayerNumber(*id));
+                }
+                *id
+            }
+            FirstPlayer::Random => PlayerId(random_below(rng, seated.len())),
+            FirstPlayer::MostRecentDate => seated
+                .iter()
+                .enumerate()
+                .filter_map(|(index, p)| p.days_since_date.map(|days| (days, index)))
+                .min()
+                .map(|(_, index)| PlayerId(index))
+                .unwrap_or(PlayerId(0)),
+        }
+    }
+}
+
+/// Arranges `profiles` so that seat `n` holds `profiles[seating[n]]`.
+#[throws]
+pub(crate) fn seat_players(profiles: Vec<PlayerProfile>, seating: &[usize]) -> Vec<PlayerProfile> {
+    let mut sorted = seating.to_vec();
+    sorted.sort_unstable();
+    if !sorted.iter().copied().eq(0..profiles.len()) {
+        throw!(Error::InvalidSeating);
+    }
+    seating
+        .iter()
+        .map(|index| profiles[*index].clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::seeded_rng;
+
+    fn profiles(names: &[&str]) -> Vec<PlayerProfile> {
+        names.iter().map(|name| PlayerProfile::new(name)).collect()
+    }
+
+    #[test]
+    #[throws]
+    fn test_seat_players() {
+        let seated = seat_players(profiles(&["Ann", "Bo", "Cy"]), &[2, 0, 1])?;
+        let names = seated.iter().map(|p| p.name()).collect::<Vec<_>>();
+        assert_eq!(vec!["Cy", "Ann", "Bo"], names);
+
+        for seating in &[&[0, 1][..], &[0, 1, 1], &[0, 1, 3], &[0, 1, 2, 3]] {
+            assert_eq!(
+                Some(Error::InvalidSeating),
+                seat_players(profiles(&["Ann", "Bo", "Cy"]), seating).err()
+            );
+        }
+    }
+
+    #[test]
+    #[throws]
+    fn test_choose_first_player() {
+        let mut rng = seeded_rng(0);
+        let players = vec![
+            PlayerProfile::new("Ann").last_date(30),
+            PlayerProfile::new("Bo"),
+            PlayerProfile::new("Cy").last_date(2),
+            PlayerProfile::new("Di").last_date(2),
+        ];
+
+        assert_eq!(
+            PlayerId(0),
+            FirstPlayer::default().choose(&players, &mut rng)?
+        );
+        assert_eq!(
+            PlayerId(3),
+            FirstPlayer::Seat(PlayerId(3)).choose(&players, &mut rng)?
+        );
+        assert_eq!(
+            Some(Error::InvalidPlayerNumber(PlayerId(4))),
+            FirstPlayer::Seat(PlayerId(4))
+                .choose(&players, &mut rng)
+                .err()
+        );
+        assert_eq!(
+            PlayerId(2),
+            FirstPlayer::MostRecentDate.choose(&players, &mut rng)?
+        );
+        assert_eq!(
+            PlayerId(0),
+            FirstPlayer::MostRecentDate.choose(&profiles(&["Ann", "Bo"]), &mut rng)?
+        );
+
+        let random = FirstPlayer::Random.choose(&players, &mut rng)?;
+        assert!(random.index() < players.len());
+    }
+}
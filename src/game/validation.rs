@@ -0,0 +1,224 @@
+use crate::game::card_action::CardAction;
+use crate::{Error, Game};
+use fehler::{throw, throws};
+
+/// The part of an action that broke a rule, so that a UI can highlight it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ActionPart {
+    /// The player taking the action.
+    Player,
+    /// The card being played.
+    Card,
+    Target,
+    SecondTarget,
+    Guess,
+    /// The state of the game, like a round that is already over.
+    Game,
+}
+
+/// A rule that an action breaks.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Violation {
+    part: ActionPart,
+    error: Error,
+}
+
+impl Violation {
+    pub fn new(part: ActionPart, error: Error) -> Violation {
+        Violation { part, error }
+    }
+
+    pub fn part(&self) -> ActionPart {
+        self.part
+    }
+
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    pub fn into_error(self) -> Error {
+        self.error
+    }
+}
+
+/// Every rule that an action breaks, in the order that `perform_action`
+/// checks them.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct ValidationReport {
+    violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    /// The violations that concern one part of the action.
+    pub fn violations_of(&self, part: ActionPart) -> impl Iterator<Item = &Violation> {
+        self.violations.iter().filter(move |v| v.part == part)
+    }
+
+    /// The error that `perform_action` would return, if any.
+    pub fn first_error(&self) -> Option<&Error> {
+        self.violations.first().map(Violation::error)
+    }
+
+    /// Returns the first error, so that a report can be used with `?`.
+    #[throws]
+    pub fn into_result(self) {
+        if let Some(violation) = self.violations.into_iter().next() {
+            throw!(violation.into_error());
+        }
+    }
+}
+
+impl Game {
+    /// Checks the action against every rule, without stopping at the first
+    /// one it breaks.
+    pub fn validate_action(&self, action: &CardAction) -> ValidationReport {
+        let mut violations = Vec::new();
+        if self.is_round_over() {
+            violations.push(Violation::new(ActionPart::Game, Error::BadActionRoundOver));
+        }
+        if self.cards_to_return > 0 {
+            violations.push(Violation::new(
+                ActionPart::Game,
+                Error::BadActionMustReturnCards(self.cards_to_return),
+            ));
+        }
+        violations.extend(self.action_violations(action));
+        ValidationReport { violations }
+    }
+
+    /// The rules that the action breaks, ignoring the state of the round.
+    pub(super) fn action_violations(&self, action: &CardAction) -> Vec<Violation> {
+        let player = match self.player(action.current()) {
+            Ok(player) => player,
+            Err(error) => return vec![Violation::new(ActionPart::Player, error)],
+        };
+        let card = action.card();
+        let mut violations = Vec::new();
+        if player.card_index(card).is_none() {
+            violations.push(Violation::new(
+                ActionPart::Card,
+                Error::BadActionPlayerDoesntHaveCard(action.current(), card.name().to_string()),
+            ));
+        }
+
+        let rules = match self.ruleset.rules_for_card(card) {
+            Ok(rules) => rules,
+            Err(error) => {
+                violations.push(Violation::new(ActionPart::Card, error));
+                return violations;
+            }
+        };
+        violations.extend(rules.action_violations(
+            action,
+            self.current_player,
+            &self.active,
+            &self.protected,
+            player.hand(),
+        ));
+        if let Ok(guess) = action.guess() {
+            // A guess must name a card from this edition.
+            let deck = self.ruleset.deck();
+            let in_deck = deck
+                .peek(deck.cards_remaining())
+                .iter()
+                .any(|c| c.is_same_card(guess));
+            if rules.guess_required() && action.has_target() && !in_deck {
+                violations.push(Violation::new(
+                    ActionPart::Guess,
+                    Error::BadActionInvalidGuess(guess.name().to_string()),
+                ));
+            }
+        }
+        if let Err(error) = self.ruleset.check_action(action, self) {
+            let part = match error {
+                Error::BadActionMustTarget(_) => ActionPart::Target,
+                _ => ActionPart::Card,
+            };
+            violations.push(Violation::new(part, error));
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::test::{deck, original};
+    use crate::state::{OriginalCard::*, PlayerId};
+
+    #[test]
+    fn test_validate_action() {
+        let mut game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Handmaid, Guard, Baron, Priest, Countess, King, Prince]),
+        )
+        .unwrap();
+        game.protected.insert(PlayerId(1));
+
+        // Cy doesn't hold a Guard, isn't the current player, can't target a
+        // protected player, and can't guess a Guard.
+        let action = CardAction::new(Guard, PlayerId(2), Some(PlayerId(1)), Some(Guard));
+        let report = game.validate_action(&action);
+        assert!(!report.is_valid());
+        let parts = report
+            .violations()
+            .iter()
+            .map(Violation::part)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ActionPart::Card,
+                ActionPart::Player,
+                ActionPart::Target,
+                ActionPart::Guess
+            ],
+            parts
+        );
+        assert_eq!(
+            Some(&Error::BadActionInvalidGuess("Guard".to_string())),
+            report
+                .violations_of(ActionPart::Guess)
+                .next()
+                .map(Violation::error)
+        );
+        assert_eq!(
+            game.is_valid_action(&action).err().as_ref(),
+            report.first_error()
+        );
+
+        let action = CardAction::new(Handmaid, PlayerId(0), None, None);
+        assert!(game.validate_action(&action).is_valid());
+    }
+
+    #[test]
+    fn test_guess_without_target() {
+        let game = Game::with_deck(
+            original(),
+            3,
+            PlayerId(0),
+            deck(&[Guard, Priest, Baron, Countess, King]),
+        )
+        .unwrap();
+
+        // Only the missing target is wrong; the guess is fine.
+        let action = CardAction::new(Guard, PlayerId(0), None, Some(Priest));
+        let report = game.validate_action(&action);
+        assert_eq!(
+            vec![Violation::new(
+                ActionPart::Target,
+                Error::BadActionMissingTarget
+            )],
+            report.violations()
+        );
+    }
+}
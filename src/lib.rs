@@ -1,47 +1,155 @@
-//use crate::state::Card;
 use thiserror::Error;
 
-
-//mod game;
-//mod messenger;
+mod game;
+mod messenger;
 mod state;
 
 #[derive(Debug, Error, Eq, PartialEq)]
 pub enum LoveLetterError {
-//     #[error("Discarding a card, {0}, that is not in the player's hand")]
-//     DiscardingCardNotInHand(Card),
-
     #[error("Internal error: player should only have one card, but they have {0}")]
     InvalidNumberOfCards(usize),
 
     #[error("Internal error: invalid card index, {0}, specified")]
     InternalErrorBadCardIndex(usize),
 
-    #[error("You can only play Love Letter with 3 or 4 players. {0} is not allowed")]
+    #[error("These rules do not allow a game with {0} players")]
     InvalidNumberOfPlayers(usize),
 
-    #[error("Invalid player number: {0}")]
-    InvalidPlayerNumber(usize),
-
-//     #[error("InternalError: unexpected empty deck")]
-//     InternalErrorUnexpectedEmptyDeck,
-
-//     #[error("Only the current player can take an action. {0} provided.")]
-//     BadActionNotCurrentPlayer(usize),
-//     #[error("Inactive player targeted: {0}")]
-//     BadActionTargetingInactive(usize),
-//     #[error("Protected player targeted: {0}")]
-//     BadActionTargetingProtected(usize),
-//     #[error("Cannot target self")]
-//     BadActionCannotTargetSelf,
-//     #[error("Missing target")]
-//     BadActionMissingTarget,
-//     #[error("Missing guess")]
-//     BadActionMissingGuess,
-//     #[error("Player {0} does not have card, {1}")]
-//     BadActionPlayerDoesntHaveCard(usize, Card),
+    #[error("{}", self.describe(|id| id.to_string()))]
+    InvalidPlayerNumber(PlayerId),
+
+    #[error("More than one player is named {0}")]
+    DuplicatePlayerName(String),
+
+    #[error("The seating order must list every player exactly once")]
+    InvalidSeating,
+
+    #[error("Unknown ruleset: {0}")]
+    UnknownRuleset(String),
+    #[error("Unknown card: {0} ({1})")]
+    UnknownCard(String, u8),
+
+    #[error("InternalError: unexpected empty deck")]
+    InternalErrorUnexpectedEmptyDeck,
+
+    #[error("Internal error: {0}")]
+    InternalErrorInvariantViolated(String),
+
+    #[error("{}", self.describe(|id| id.to_string()))]
+    BadActionNotCurrentPlayer(PlayerId),
+    #[error("{}", self.describe(|id| id.to_string()))]
+    BadActionTargetingInactive(PlayerId),
+    #[error("{}", self.describe(|id| id.to_string()))]
+    BadActionTargetingProtected(PlayerId),
+    #[error("Cannot target self")]
+    BadActionCannotTargetSelf,
+    #[error("Missing target")]
+    BadActionMissingTarget,
+    #[error("Missing guess")]
+    BadActionMissingGuess,
+    #[error("Missing second target")]
+    BadActionMissingSecondTarget,
+    #[error("This card does not take a target")]
+    BadActionUnexpectedTarget,
+    #[error("This card does not take a second target")]
+    BadActionUnexpectedSecondTarget,
+    #[error("This card does not take a guess")]
+    BadActionUnexpectedGuess,
+    #[error("{}", self.describe(|id| id.to_string()))]
+    BadActionDuplicateTarget(PlayerId),
+    #[error("You cannot guess {0}")]
+    BadActionInvalidGuess(String),
+    #[error("{}", self.describe(|id| id.to_string()))]
+    BadActionMustTarget(PlayerId),
+    #[error("{}", self.describe(|id| id.to_string()))]
+    BadActionPlayerDoesntHaveCard(PlayerId, String),
+    #[error("Unknown card: {0}")]
+    BadActionUnknownCard(String),
+    #[error("You must play the {0}")]
+    BadActionMustPlayCard(String),
+    #[error("You must return {0} cards to the bottom of the deck")]
+    BadActionMustReturnCards(usize),
+    #[error("There are no cards to return")]
+    BadActionNoCardsToReturn,
+    #[error("The round is over")]
+    BadActionRoundOver,
+    #[error("The round is not over yet")]
+    RoundNotOver,
+    #[error("The match is over")]
+    MatchOver,
+
+    #[error("There is nothing to undo")]
+    NothingToUndo,
+    #[error("There is nothing to redo")]
+    NothingToRedo,
+    #[error("That action revealed hidden cards, so it cannot be undone")]
+    UndoAfterHiddenInfoRevealed,
+
+    #[error("This game was not dealt from a seed, so it cannot be replayed")]
+    NoSeed,
+    #[error("Action {0} of the replay is not allowed: {1}")]
+    BadReplayAction(usize, String),
+    #[error("The replay has no position {0}")]
+    InvalidReplayPosition(usize),
+
+    #[error("Enter a card to play")]
+    CommandEmpty,
+    #[error("No card is called {0}")]
+    CommandUnknownCard(String),
+    #[error("{0} could be {}", .1.join(" or "))]
+    CommandAmbiguousCard(String, Vec<String>),
+    #[error("No player is called {0}")]
+    CommandUnknownPlayer(String),
+    #[error("{0} needs a target")]
+    CommandNeedsTarget(String),
+    #[error("{0} needs a second target")]
+    CommandNeedsSecondTarget(String),
+    #[error("{0} needs a guess")]
+    CommandNeedsGuess(String),
+    #[error("Did not expect {0}")]
+    CommandUnexpected(String),
+}
+
+impl LoveLetterError {
+    /// The error's message, using `name_of` to name players. `Display`
+    /// names them by seat number.
+    pub fn describe(&self, name_of: impl Fn(PlayerId) -> String) -> String {
+        match self {
+            Error::InvalidPlayerNumber(id) => format!("Invalid player: {}", name_of(*id)),
+            Error::BadActionNotCurrentPlayer(id) => format!(
+                "Only the current player can take an action. {} provided.",
+                name_of(*id)
+            ),
+            Error::BadActionTargetingInactive(id) => {
+                format!("Inactive player targeted: {}", name_of(*id))
+            }
+            Error::BadActionTargetingProtected(id) => {
+                format!("Protected player targeted: {}", name_of(*id))
+            }
+            Error::BadActionDuplicateTarget(id) => {
+                format!("{} cannot be targeted twice", name_of(*id))
+            }
+            Error::BadActionMustTarget(id) => format!("{} must be targeted", name_of(*id)),
+            Error::BadActionPlayerDoesntHaveCard(id, card) => {
+                format!("{} does not have card, {}", name_of(*id), card)
+            }
+            _ => self.to_string(),
+        }
+    }
 }
 
 use LoveLetterError as Error;
 
-//pub use game::{CardAction, Game, GameBuilder};
+pub use game::{
+    builtin_ruleset, ActionPart, Audience, CardAction, CardRules, EliminationReason, FirstPlayer,
+    Game, GameAction, GameBuilder, GameEvent, HandConstraint, Match, OriginalRuleset,
+    PlayerProfile, PlayerSummary, PlayerView, PremiumRuleset, Replay, RevealedCard, RevisedRuleset,
+    RoundEndReason, RoundOutcome, Ruleset, SecondTarget, UndoPolicy, ValidationReport, Violation,
+};
+#[cfg(feature = "serde")]
+pub use game::{SavedAction, SavedGame, SavedReplay};
+pub use messenger::Messenger;
+pub use state::{
+    Card, CardId, CardLocation, CardRegistry, Deck, IdentifiedCard, Named, OriginalCard, Player,
+    PlayerId, PremiumCard, RevisedCard, Table,
+};
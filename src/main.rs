@@ -1,10 +1,8 @@
-//use loveletter::{GameBuilder};
+use loveletter::GameBuilder;
 
 fn main() -> std::result::Result<(), loveletter::LoveLetterError> {
-    // let game = GameBuilder::default()
-    //     .num_players(4)
-    //     .build()?;
+    let game = GameBuilder::default().num_players(4).build()?;
 
-    // println!("GAME: {}", game);
+    println!("GAME: {}", game);
     Ok(())
 }
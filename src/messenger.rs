@@ -1,4 +1,6 @@
+use crate::game::GameEvent;
 use crate::state::Player;
+use crate::Game;
 
 /// Trait describing a mechanism for sending messages.
 /// Briefly, messages may be sent to a single player or all players.
@@ -15,21 +17,32 @@ pub trait Messenger {
     fn to_player(&mut self, player: &Player, msg: &str) {
         self.message(Some(player), msg);
     }
+
+    /// Called for every event in the game. By default, the event is
+    /// described in English and sent with `message`. Override this to
+    /// receive the events themselves.
+    fn event(&mut self, game: &Game, event: &GameEvent) {
+        event.send_text(game, self);
+    }
 }
 
 #[cfg(test)]
-mod test {
+pub mod test {
     use super::*;
     use crate::Error;
     use fehler::throws;
 
-    struct TestMessenger {
+    pub struct TestMessenger {
         pub messages: Vec<String>,
+        pub events: Vec<GameEvent>,
     }
 
     impl TestMessenger {
-        fn new() -> TestMessenger {
-            TestMessenger { messages: vec![] }
+        pub fn new() -> TestMessenger {
+            TestMessenger {
+                messages: vec![],
+                events: vec![],
+            }
         }
 
         fn player_name<'a>(&self, player: Option<&'a Player>) -> &'a str {
@@ -45,6 +58,11 @@ mod test {
             let saved = format!("{}: {}", self.player_name(player), msg);
             self.messages.push(saved);
         }
+
+        fn event(&mut self, game: &Game, event: &GameEvent) {
+            self.events.push(event.clone());
+            event.send_text(game, self);
+        }
     }
 
     #[test]
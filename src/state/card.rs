@@ -1,3 +1,4 @@
+use crate::state::identified_card::CardId;
 use objekt_clonable::clonable;
 
 #[clonable]
@@ -15,13 +16,25 @@ pub trait Card: std::fmt::Debug + std::fmt::Display + std::clone::Clone {
     /// (In some games, there may be different cards with the same value.
     /// To check for that condition, use `has_same_value()`.
     fn is_same_card(&self, other: &dyn Card) -> bool {
-	self.name() == other.name() &&
-	    self.value() == other.value()
+        self.name() == other.name() && self.value() == other.value()
     }
 
     /// Compares the `value` of two Cards.
     fn has_same_value(&self, other: &dyn Card) -> bool {
-	self.value() == other.value()
+        self.value() == other.value()
+    }
+
+    /// The id of the physical card, if it has one. Cards dealt from a Deck
+    /// always have an id.
+    fn id(&self) -> Option<CardId> {
+        None
+    }
+
+    /// Compares two Cards to see if they are the same physical card.
+    ///
+    /// Cards without an id are never the same instance.
+    fn is_same_instance(&self, other: &dyn Card) -> bool {
+        self.id().is_some() && self.id() == other.id()
     }
 }
 
@@ -41,9 +54,9 @@ mod test {
 
     #[test]
     fn test_clone() {
-	let nigel = TestCard::boxed("Nigel", 3);
-	let junior = nigel.clone();
+        let nigel = TestCard::boxed("Nigel", 3);
+        let junior = nigel.clone();
 
-	assert!(nigel.is_same_card(junior.as_ref()));
+        assert!(nigel.is_same_card(junior.as_ref()));
     }
 }
@@ -0,0 +1,72 @@
+use crate::state::card::Card;
+use crate::state::original_card::OriginalCard;
+use crate::state::premium_card::PremiumCard;
+use crate::state::revised_card::RevisedCard;
+
+/// Finds cards by name and value.
+///
+/// A `Box<dyn Card>` can't be rebuilt from its name and value alone, so
+/// anything that recreates cards, like loading a saved game, looks them
+/// up here.
+#[derive(Clone, Debug, Default)]
+pub struct CardRegistry {
+    cards: Vec<Box<dyn Card>>,
+}
+
+impl CardRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> CardRegistry {
+        CardRegistry::default()
+    }
+
+    /// Creates a registry holding the cards from every edition in this crate.
+    pub fn with_builtin_cards() -> CardRegistry {
+        let mut registry = CardRegistry::new();
+        registry.register(&OriginalCard::ALL);
+        registry.register(&PremiumCard::ALL);
+        registry.register(&RevisedCard::ALL);
+        registry
+    }
+
+    /// Adds cards to the registry. A card is ignored if the registry already
+    /// holds the same card.
+    pub fn register<C: Card + Clone + 'static>(&mut self, cards: &[C]) {
+        for card in cards {
+            if self.find(card.name(), card.value()).is_none() {
+                self.cards.push(Box::new(card.clone()));
+            }
+        }
+    }
+
+    /// Returns a new copy of the card with the given name and value.
+    pub fn find(&self, name: &str, value: u8) -> Option<Box<dyn Card>> {
+        self.cards
+            .iter()
+            .find(|c| c.name() == name && c.value() == value)
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::testcard::TestCard;
+
+    #[test]
+    fn test_registry() {
+        let mut registry = CardRegistry::with_builtin_cards();
+        let guard = registry.find("Guard", 1).unwrap();
+        assert!(OriginalCard::Guard.is_same_card(guard.as_ref()));
+        assert!(registry.find("Chancellor", 6).is_some());
+        assert!(registry.find("Bishop", 9).is_some());
+        assert!(registry.find("Princess", 9).is_some());
+        assert!(registry.find("Princess", 6).is_none());
+
+        assert!(registry.find("Frank", 1).is_none());
+        registry.register(&[TestCard::new("Frank", 1)]);
+        assert_eq!(
+            "TestCard: 1:Frank",
+            registry.find("Frank", 1).unwrap().to_string()
+        );
+    }
+}
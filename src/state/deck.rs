@@ -1,13 +1,14 @@
 use crate::state::card::Card;
-use rand::seq::SliceRandom;
-use rand::thread_rng;
+use crate::state::identified_card::{CardId, IdentifiedCard};
+use rand::{thread_rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use std::fmt::{Display, Formatter};
 
 /// A deck of LoveLetter cards.
 ///
 /// Enforces no rules, but allows only operations that would occur in a
 /// game of LoveLetter.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Deck {
     // Cards are stored in reverse order so that we can deal off the
     // efficient end.
@@ -18,7 +19,27 @@ impl Deck {
     /// Create a new Deck containing the specified cards.
     ///
     /// The cards will be dealt in the order that they are in the input vector.
-    pub fn new(mut cards: Vec<Box<dyn Card>>) -> Deck {
+    /// Each card without an id is given one, counting up from above the
+    /// highest id already in the vector, so that no two cards share an id.
+    /// When no card has an id, each card's id is its position.
+    pub fn new(cards: Vec<Box<dyn Card>>) -> Deck {
+        let mut next_id = cards
+            .iter()
+            .filter_map(|card| card.id())
+            .map(|id| id.0 + 1)
+            .max()
+            .unwrap_or(0);
+        let mut cards = cards
+            .into_iter()
+            .map(|card| {
+                if card.id().is_some() {
+                    card
+                } else {
+                    next_id += 1;
+                    Box::new(IdentifiedCard::new(CardId(next_id - 1), card)) as Box<dyn Card>
+                }
+            })
+            .collect::<Vec<_>>();
         cards.reverse();
         Deck { cards }
     }
@@ -35,11 +56,106 @@ impl Deck {
         self.cards.pop()
     }
 
+    /// Deals up to `count` cards off the top of the deck, in the order they
+    /// would have been dealt. If the deck runs out, fewer cards are returned.
+    pub fn deal(&mut self, count: usize) -> Vec<Box<dyn Card>> {
+        let split = self.cards.len().saturating_sub(count);
+        let mut dealt = self.cards.split_off(split);
+        dealt.reverse();
+        dealt
+    }
+
+    /// Removes exactly `count` cards from the top of the deck to be placed
+    /// face up. If there aren't enough cards, returns None and leaves the
+    /// deck untouched.
+    pub fn burn(&mut self, count: usize) -> Option<Vec<Box<dyn Card>>> {
+        if count > self.cards.len() {
+            None
+        } else {
+            Some(self.deal(count))
+        }
+    }
+
+    /// Returns up to `count` cards from the top of the deck, in the order
+    /// they will be dealt, without removing them.
+    pub fn peek(&self, count: usize) -> Vec<&dyn Card> {
+        self.cards
+            .iter()
+            .rev()
+            .take(count)
+            .map(|c| c.as_ref())
+            .collect()
+    }
+
+    /// Removes the copy of `card` closest to the top of the deck. Returns
+    /// None if the deck doesn't contain the card.
+    pub fn take_card(&mut self, card: &dyn Card) -> Option<Box<dyn Card>> {
+        let index = self.cards.iter().rposition(|c| c.is_same_card(card))?;
+        Some(self.cards.remove(index))
+    }
+
+    /// Iterates over the cards remaining in the deck. The cards are sorted by
+    /// value and name, so the iteration reveals nothing about the order in
+    /// which they will be dealt.
+    pub fn remaining(&self) -> impl Iterator<Item = &dyn Card> {
+        let mut cards = self.cards.iter().map(|c| c.as_ref()).collect::<Vec<_>>();
+        cards.sort_by(|a, b| (a.value(), a.name()).cmp(&(b.value(), b.name())));
+        cards.into_iter()
+    }
+
+    /// Puts a card on the "bottom" of the deck. It will be dealt after
+    /// every other card.
+    pub fn put_on_bottom(&mut self, card: Box<dyn Card>) {
+        self.cards.insert(0, card);
+    }
+
     /// Shuffles the cards in the deck.
     ///
     /// After shuffling, the cards will be dealt in a random order.
     pub fn shuffle(&mut self) {
-        self.cards.shuffle(&mut thread_rng());
+        self.shuffle_with(&mut thread_rng());
+    }
+
+    /// Shuffles the cards in the deck using the supplied random number
+    /// generator.
+    ///
+    /// The shuffle is a Fisher-Yates shuffle that uses only the raw output
+    /// of `rng`, so the same generator, in the same state, will always
+    /// produce the same order, regardless of the version of `rand`.
+    pub fn shuffle_with(&mut self, rng: &mut dyn RngCore) {
+        for i in (1..self.cards.len()).rev() {
+            let j = random_below(rng, i + 1);
+            self.cards.swap(i, j);
+        }
+    }
+
+    /// Shuffles the cards in the deck into an order determined entirely by
+    /// `seed`. The same seed produces the same order on every machine.
+    pub fn shuffle_with_seed(&mut self, seed: u64) {
+        self.shuffle_with(&mut seeded_rng(seed));
+    }
+}
+
+/// Creates a portable random number generator from a seed.
+///
+/// ChaCha20's output is fully specified, and the seed is expanded by hand,
+/// so the generator's output never changes.
+pub fn seeded_rng(seed: u64) -> ChaCha20Rng {
+    let mut bytes = [0; 32];
+    bytes[..8].copy_from_slice(&seed.to_le_bytes());
+    ChaCha20Rng::from_seed(bytes)
+}
+
+/// Returns a uniformly distributed number in 0..bound, rejecting values
+/// that would bias the result toward low numbers.
+pub(crate) fn random_below(rng: &mut dyn RngCore, bound: usize) -> usize {
+    let bound = bound as u64;
+    let limit = u64::MAX - u64::MAX % bound;
+    loop {
+        let value = rng.next_u64();
+        if value < limit {
+            return (value % bound) as usize;
+        }
     }
 }
 
@@ -68,9 +184,9 @@ mod test {
     #[test]
     fn test_basic() {
         let mut deck = Deck::new(vec![
-	    TestCard::boxed("One", 1),
-	    TestCard::boxed("Two", 2),
-	    TestCard::boxed("Three", 3),
+            TestCard::boxed("One", 1),
+            TestCard::boxed("Two", 2),
+            TestCard::boxed("Three", 3),
         ]);
 
         assert_eq!(3, deck.cards_remaining());
@@ -82,19 +198,150 @@ mod test {
         assert_eq!(0, deck.cards_remaining());
     }
 
+    fn names(cards: &[Box<dyn Card>]) -> Vec<&str> {
+        cards.iter().map(|c| c.name()).collect()
+    }
+
+    fn one_to_four() -> Deck {
+        Deck::new(vec![
+            TestCard::boxed("One", 1),
+            TestCard::boxed("Two", 2),
+            TestCard::boxed("Three", 3),
+            TestCard::boxed("Four", 4),
+        ])
+    }
+
+    #[test]
+    fn test_deal() {
+        let mut deck = one_to_four();
+
+        assert_eq!(vec!["One", "Two"], names(&deck.deal(2)));
+        assert_eq!(Vec::<&str>::new(), names(&deck.deal(0)));
+        assert_eq!(vec!["Three", "Four"], names(&deck.deal(5)));
+        assert!(deck.deal(1).is_empty());
+    }
+
+    #[test]
+    fn test_burn() {
+        let mut deck = one_to_four();
+
+        assert!(deck.burn(5).is_none());
+        assert_eq!(4, deck.cards_remaining());
+        assert_eq!(vec!["One", "Two", "Three"], names(&deck.burn(3).unwrap()));
+        assert_eq!("Four", deck.deal_one().unwrap().name());
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut deck = one_to_four();
+
+        let peeked = deck.peek(3).iter().map(|c| c.name()).collect::<Vec<_>>();
+        assert_eq!(vec!["One", "Two", "Three"], peeked);
+        assert_eq!(4, deck.cards_remaining());
+        assert_eq!(4, deck.peek(10).len());
+
+        deck.put_on_bottom(TestCard::boxed("Five", 5));
+        assert_eq!("One", deck.peek(1)[0].name());
+        assert_eq!("Five", deck.peek(5)[4].name());
+    }
+
+    #[test]
+    fn test_take_card() {
+        let mut deck = Deck::new(vec![
+            TestCard::boxed("One", 1),
+            TestCard::boxed("Two", 2),
+            TestCard::boxed("One", 1),
+            TestCard::boxed("Three", 3),
+        ]);
+
+        let one = TestCard::boxed("One", 1);
+        assert!(deck.take_card(one.as_ref()).is_some());
+        assert_eq!(vec!["Two", "One", "Three"], names(&deck.deal(3)));
+        assert!(deck.take_card(one.as_ref()).is_none());
+    }
+
+    #[test]
+    fn test_remaining() {
+        let mut deck = Deck::new(vec![
+            TestCard::boxed("Three", 3),
+            TestCard::boxed("One", 1),
+            TestCard::boxed("Two", 2),
+            TestCard::boxed("Deux", 2),
+        ]);
+
+        let remaining = deck.remaining().map(|c| c.name()).collect::<Vec<_>>();
+        assert_eq!(vec!["One", "Deux", "Two", "Three"], remaining);
+
+        deck.deal_one();
+        assert_eq!(3, deck.remaining().count());
+    }
+
+    #[test]
+    fn test_card_ids() {
+        let mut deck = Deck::new(vec![
+            TestCard::boxed("Guard", 1),
+            TestCard::boxed("Guard", 1),
+            Box::new(IdentifiedCard::new(CardId(7), TestCard::boxed("Guard", 1))),
+        ]);
+        let top = deck.deal_one().unwrap();
+        deck.put_on_bottom(top);
+
+        let ids = deck.deal(3).iter().map(|c| c.id()).collect::<Vec<_>>();
+        assert_eq!(vec![Some(CardId(9)), Some(CardId(7)), Some(CardId(8))], ids);
+
+        // A new card never takes the id of a card that already has one.
+        let mut deck = Deck::new(vec![
+            TestCard::boxed("Guard", 1),
+            TestCard::boxed("Guard", 1),
+            TestCard::boxed("Guard", 1),
+            Box::new(IdentifiedCard::new(CardId(0), TestCard::boxed("Guard", 1))),
+        ]);
+        let ids = deck.deal(4).iter().map(|c| c.id()).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                Some(CardId(1)),
+                Some(CardId(2)),
+                Some(CardId(3)),
+                Some(CardId(0))
+            ],
+            ids
+        );
+
+        let deck = Deck::new(vec![
+            TestCard::boxed("Guard", 1),
+            TestCard::boxed("Guard", 1),
+        ]);
+        let ids = deck.peek(2).iter().map(|c| c.id()).collect::<Vec<_>>();
+        assert_eq!(vec![Some(CardId(0)), Some(CardId(1))], ids);
+    }
+
+    #[test]
+    fn test_put_on_bottom() {
+        let mut deck = Deck::new(vec![TestCard::boxed("One", 1), TestCard::boxed("Two", 2)]);
+
+        deck.put_on_bottom(TestCard::boxed("Three", 3));
+        deck.put_on_bottom(TestCard::boxed("Four", 4));
+
+        assert_eq!(4, deck.cards_remaining());
+        assert_eq!("One", deck.deal_one().unwrap().name());
+        assert_eq!("Two", deck.deal_one().unwrap().name());
+        assert_eq!("Three", deck.deal_one().unwrap().name());
+        assert_eq!("Four", deck.deal_one().unwrap().name());
+    }
+
     #[test]
     fn test_shuffle() {
         // We aren't testing the randomness or anything.
         // We just want to make sure that all of the cards are in the shuffled
         // deck exactly once.
         let mut deck = Deck::new(vec![
-	    TestCard::boxed("One", 1),
-	    TestCard::boxed("Two", 2),
-	    TestCard::boxed("Three", 3),
-	    TestCard::boxed("Four", 4),
-	    TestCard::boxed("Five", 5),
-	    TestCard::boxed("Six", 6),
-	    TestCard::boxed("Seven", 7),
+            TestCard::boxed("One", 1),
+            TestCard::boxed("Two", 2),
+            TestCard::boxed("Three", 3),
+            TestCard::boxed("Four", 4),
+            TestCard::boxed("Five", 5),
+            TestCard::boxed("Six", 6),
+            TestCard::boxed("Seven", 7),
         ]);
 
         deck.shuffle();
@@ -106,4 +353,46 @@ mod test {
             &card_names
         );
     }
+
+    fn numbered_deck(count: u8) -> Deck {
+        Deck::new(
+            (0..count)
+                .map(|value| TestCard::boxed("Number", value) as Box<dyn Card>)
+                .collect(),
+        )
+    }
+
+    fn values(mut deck: Deck) -> Vec<u8> {
+        let mut values = vec![];
+        while let Some(card) = deck.deal_one() {
+            values.push(card.value());
+        }
+        values
+    }
+
+    #[test]
+    fn test_shuffle_with_seed() {
+        let mut first = numbered_deck(16);
+        let mut second = numbered_deck(16);
+        let mut third = numbered_deck(16);
+
+        first.shuffle_with_seed(1);
+        second.shuffle_with(&mut seeded_rng(1));
+        third.shuffle_with_seed(2);
+
+        let first = values(first);
+        assert_eq!(first, values(second));
+        assert_ne!(first, values(third));
+    }
+
+    #[test]
+    fn test_shuffle_is_stable() {
+        // If this test fails, saved seeds will no longer reproduce their games.
+        let mut deck = numbered_deck(16);
+        deck.shuffle_with_seed(42);
+        assert_eq!(
+            vec![0, 13, 9, 1, 4, 12, 15, 2, 3, 8, 10, 7, 11, 5, 14, 6],
+            values(deck)
+        );
+    }
 }
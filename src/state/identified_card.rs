@@ -0,0 +1,81 @@
+use crate::state::card::Card;
+use std::fmt::{Display, Formatter};
+
+/// Identifies one physical card. Two Guards are the same card, but they
+/// never have the same CardId.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CardId(pub u32);
+
+impl Display for CardId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// A card tagged with the id of the physical card that it represents.
+///
+/// IdentifiedCard behaves exactly like the card it wraps, so the rules
+/// never need to know that it is there.
+#[derive(Clone, Debug)]
+pub struct IdentifiedCard {
+    id: CardId,
+    card: Box<dyn Card>,
+}
+
+impl IdentifiedCard {
+    pub fn new(id: CardId, card: Box<dyn Card>) -> IdentifiedCard {
+        IdentifiedCard { id, card }
+    }
+
+    /// The card, without its id.
+    pub fn card(&self) -> &dyn Card {
+        self.card.as_ref()
+    }
+}
+
+impl Card for IdentifiedCard {
+    fn name(&self) -> &str {
+        self.card.name()
+    }
+
+    fn value(&self) -> u8 {
+        self.card.value()
+    }
+
+    fn id(&self) -> Option<CardId> {
+        Some(self.id)
+    }
+}
+
+impl Display for IdentifiedCard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.card)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::testcard::TestCard;
+
+    #[test]
+    fn test_identified_card() {
+        let first = IdentifiedCard::new(CardId(1), TestCard::boxed("Guard", 1));
+        let second = IdentifiedCard::new(CardId(2), TestCard::boxed("Guard", 1));
+        let plain = TestCard::new("Guard", 1);
+
+        assert_eq!("Guard", first.name());
+        assert_eq!(1, first.value());
+        assert_eq!(Some(CardId(1)), first.id());
+        assert_eq!(None, plain.id());
+
+        assert!(first.is_same_card(&second));
+        assert!(first.is_same_card(&plain));
+        assert!(!first.is_same_instance(&second));
+        assert!(first.is_same_instance(&first.clone()));
+        assert!(!plain.is_same_instance(&plain));
+
+        assert_eq!(plain.to_string(), first.to_string());
+    }
+}
@@ -1,17 +1,30 @@
 //! Module representing the physical state of a LoveLetter game.
-//! 
+//!
 //! State has no concept of the game rules, and nothing prevents creating
-//! illegal game states. 
+//! illegal game states.
 
 mod card;
+mod card_registry;
 mod deck;
+mod identified_card;
+mod original_card;
 mod player;
+mod player_id;
+mod premium_card;
+mod revised_card;
 mod table;
 
 #[cfg(test)]
 mod testcard;
 
 pub use card::Card;
-pub use deck::Deck;
+pub use card_registry::CardRegistry;
+pub(crate) use deck::random_below;
+pub use deck::{seeded_rng, Deck};
+pub use identified_card::{CardId, IdentifiedCard};
+pub use original_card::OriginalCard;
 pub use player::Player;
-pub use table::Table;
+pub use player_id::PlayerId;
+pub use premium_card::PremiumCard;
+pub use revised_card::RevisedCard;
+pub use table::{CardLocation, Named, Table};
@@ -0,0 +1,153 @@
+use crate::state::card::Card;
+use crate::state::deck::Deck;
+use std::fmt::{Display, Formatter};
+
+/// The eight cards from the original edition of Love Letter.
+///
+/// The variants are declared in order of value, so comparing two
+/// OriginalCards compares their values.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum OriginalCard {
+    Guard,
+    Priest,
+    Baron,
+    Handmaid,
+    Prince,
+    King,
+    Countess,
+    Princess,
+}
+
+impl OriginalCard {
+    /// Every card in the original edition, in order of value.
+    pub const ALL: [OriginalCard; 8] = [
+        OriginalCard::Guard,
+        OriginalCard::Priest,
+        OriginalCard::Baron,
+        OriginalCard::Handmaid,
+        OriginalCard::Prince,
+        OriginalCard::King,
+        OriginalCard::Countess,
+        OriginalCard::Princess,
+    ];
+
+    /// The rules text printed on the card.
+    pub fn text(&self) -> &'static str {
+        match self {
+            OriginalCard::Guard => "Name a non-Guard card and choose another player. If that player has that card, he or she is out of the round.",
+            OriginalCard::Priest => "Look at another player's hand.",
+            OriginalCard::Baron => "You and another player secretly compare hands. The player with the lower value is out of the round.",
+            OriginalCard::Handmaid => "Until your next turn, ignore all effects from other players' cards.",
+            OriginalCard::Prince => "Choose any player (including yourself) to discard his or her hand and draw a new card.",
+            OriginalCard::King => "Trade hands with another player of your choice.",
+            OriginalCard::Countess => "If you have this card and the King or Prince in your hand, you must discard this card.",
+            OriginalCard::Princess => "If you discard this card, you are out of the round.",
+        }
+    }
+
+    /// The number of copies of this card in a standard deck.
+    pub fn count(&self) -> usize {
+        match self {
+            OriginalCard::Guard => 5,
+            OriginalCard::Priest => 2,
+            OriginalCard::Baron => 2,
+            OriginalCard::Handmaid => 2,
+            OriginalCard::Prince => 2,
+            OriginalCard::King => 1,
+            OriginalCard::Countess => 1,
+            OriginalCard::Princess => 1,
+        }
+    }
+
+    /// Returns the OriginalCard that is the same card as `card`, if any.
+    pub fn from_card(card: &dyn Card) -> Option<OriginalCard> {
+        OriginalCard::ALL
+            .iter()
+            .find(|c| c.is_same_card(card))
+            .copied()
+    }
+
+    /// Builds the standard, unshuffled, 16-card deck.
+    pub fn deck() -> Deck {
+        Deck::new(
+            OriginalCard::ALL
+                .iter()
+                .flat_map(|card| (0..card.count()).map(move |_| Box::new(*card) as Box<dyn Card>))
+                .collect(),
+        )
+    }
+}
+
+impl Card for OriginalCard {
+    fn name(&self) -> &str {
+        match self {
+            OriginalCard::Guard => "Guard",
+            OriginalCard::Priest => "Priest",
+            OriginalCard::Baron => "Baron",
+            OriginalCard::Handmaid => "Handmaid",
+            OriginalCard::Prince => "Prince",
+            OriginalCard::King => "King",
+            OriginalCard::Countess => "Countess",
+            OriginalCard::Princess => "Princess",
+        }
+    }
+
+    fn value(&self) -> u8 {
+        match self {
+            OriginalCard::Guard => 1,
+            OriginalCard::Priest => 2,
+            OriginalCard::Baron => 3,
+            OriginalCard::Handmaid => 4,
+            OriginalCard::Prince => 5,
+            OriginalCard::King => 6,
+            OriginalCard::Countess => 7,
+            OriginalCard::Princess => 8,
+        }
+    }
+}
+
+impl Display for OriginalCard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::testcard::TestCard;
+
+    #[test]
+    fn test_values() {
+        let values = OriginalCard::ALL
+            .iter()
+            .map(|c| c.value())
+            .collect::<Vec<_>>();
+        assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8], values);
+        assert!(OriginalCard::Guard < OriginalCard::Princess);
+    }
+
+    #[test]
+    fn test_from_card() {
+        assert_eq!(
+            Some(OriginalCard::Baron),
+            OriginalCard::from_card(&TestCard::new("Baron", 3))
+        );
+        assert_eq!(None, OriginalCard::from_card(&TestCard::new("Baron", 4)));
+        assert_eq!(None, OriginalCard::from_card(&TestCard::new("Frank", 3)));
+    }
+
+    #[test]
+    fn test_deck() {
+        let mut deck = OriginalCard::deck();
+        assert_eq!(16, deck.cards_remaining());
+
+        let mut dealt = vec![];
+        while let Some(card) = deck.deal_one() {
+            dealt.push(OriginalCard::from_card(card.as_ref()).unwrap());
+        }
+        for card in OriginalCard::ALL.iter() {
+            assert_eq!(card.count(), dealt.iter().filter(|c| *c == card).count());
+        }
+    }
+}
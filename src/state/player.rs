@@ -36,11 +36,27 @@ impl Player {
         &self.name
     }
 
+    /// Returns the cards in the Player's hand.
+    pub fn hand(&self) -> &[Box<dyn Card>] {
+        &self.hand
+    }
+
+    /// Returns the cards in the Player's discard pile, in the order
+    /// they were discarded.
+    pub fn discards(&self) -> &[Box<dyn Card>] {
+        &self.discards
+    }
+
     /// Adds a card to the Player's hand.
     pub fn add_card_to_hand(&mut self, card: Box<dyn Card>) {
         self.hand.push(card);
     }
 
+    /// Adds a card to the top of the Player's discard pile.
+    pub fn add_card_to_discards(&mut self, card: Box<dyn Card>) {
+        self.discards.push(card);
+    }
+
     /// Returns the Card in the Player's hand, iff the Player has a
     /// single card. Otherwise, throws an Err.
     #[throws]
@@ -51,6 +67,12 @@ impl Player {
         self.hand[0].as_ref()
     }
 
+    /// Returns the index of `card` in the Player's hand, or None if
+    /// the Player is not holding that card.
+    pub fn card_index(&self, card: &dyn Card) -> Option<usize> {
+        self.hand.iter().position(|c| c.is_same_card(card))
+    }
+
     /// Returns the Card at the specified index in the Player's hand.
     /// Throws an Err on a bad index.
     #[throws]
@@ -62,6 +84,27 @@ impl Player {
             throw!(Error::InternalErrorBadCardIndex(index));
         }
     }
+
+    /// Removes the Card at the specified index from the Player's hand,
+    /// without discarding it. Throws an Err on a bad index.
+    #[throws]
+    pub fn take_from_hand(&mut self, index: usize) -> Box<dyn Card> {
+        if index < self.hand.len() {
+            self.hand.remove(index)
+        } else {
+            throw!(Error::InternalErrorBadCardIndex(index));
+        }
+    }
+
+    /// Exchanges this Player's hand with another Player's hand.
+    pub fn swap_hands(&mut self, other: &mut Player) {
+        std::mem::swap(&mut self.hand, &mut other.hand);
+    }
+
+    /// Moves every card in the Player's hand to their discard pile.
+    pub fn discard_hand(&mut self) {
+        self.discards.append(&mut self.hand);
+    }
 }
 
 impl Display for Player {
@@ -165,4 +208,29 @@ mod test {
             .find(|c| c.is_same_card(baron_for_testing().as_ref()))
             .is_some());
     }
+
+    #[test]
+    fn test_discard_hand() {
+        let mut player = Player::with_name("Lady Luck");
+        player.add_card_to_hand(princess_for_testing());
+        player.add_card_to_hand(baron_for_testing());
+
+        player.discard_hand();
+        assert!(player.hand().is_empty());
+        assert_eq!(2, player.discards.len());
+    }
+
+    #[throws]
+    #[test]
+    fn test_take_from_hand() {
+        let mut player = Player::with_name("Sleight of Hand");
+        player.add_card_to_hand(princess_for_testing());
+        player.add_card_to_hand(baron_for_testing());
+
+        assert!(player.take_from_hand(2).is_err());
+        let card = player.take_from_hand(0)?;
+        assert!(card.is_same_card(princess_for_testing().as_ref()));
+        assert_eq!(1, player.hand.len());
+        assert!(player.discards.is_empty());
+    }
 }
@@ -0,0 +1,48 @@
+use std::fmt::{Display, Formatter};
+
+/// Identifies a player by their seat at the table.
+///
+/// Seats are numbered from 0, but players are displayed counting from 1,
+/// the way that people count, so `PlayerId(0)` displays as "Player 1".
+/// Use `Table::display` or `Game::display` to show a player's name.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerId(pub usize);
+
+impl PlayerId {
+    /// The player's seat, counting from 0.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+impl From<usize> for PlayerId {
+    fn from(index: usize) -> PlayerId {
+        PlayerId(index)
+    }
+}
+
+impl From<PlayerId> for usize {
+    fn from(id: PlayerId) -> usize {
+        id.0
+    }
+}
+
+impl Display for PlayerId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Player {}", self.0 + 1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_player_id() {
+        let id = PlayerId::from(2);
+        assert_eq!(2, id.index());
+        assert_eq!(2, usize::from(id));
+        assert_eq!("Player 3", id.to_string());
+    }
+}
@@ -0,0 +1,186 @@
+use crate::state::card::Card;
+use crate::state::deck::Deck;
+use std::fmt::{Display, Formatter};
+
+/// The seventeen cards from the premium edition of Love Letter.
+///
+/// The premium edition includes every card from the original edition,
+/// plus nine new cards. The variants are declared in order of value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum PremiumCard {
+    Jester,
+    Assassin,
+    Guard,
+    Cardinal,
+    Priest,
+    Baroness,
+    Baron,
+    Sycophant,
+    Handmaid,
+    Count,
+    Prince,
+    Constable,
+    King,
+    DowagerQueen,
+    Countess,
+    Princess,
+    Bishop,
+}
+
+impl PremiumCard {
+    /// Every card in the premium edition, in order of value.
+    pub const ALL: [PremiumCard; 17] = [
+        PremiumCard::Jester,
+        PremiumCard::Assassin,
+        PremiumCard::Guard,
+        PremiumCard::Cardinal,
+        PremiumCard::Priest,
+        PremiumCard::Baroness,
+        PremiumCard::Baron,
+        PremiumCard::Sycophant,
+        PremiumCard::Handmaid,
+        PremiumCard::Count,
+        PremiumCard::Prince,
+        PremiumCard::Constable,
+        PremiumCard::King,
+        PremiumCard::DowagerQueen,
+        PremiumCard::Countess,
+        PremiumCard::Princess,
+        PremiumCard::Bishop,
+    ];
+
+    /// The rules text printed on the card.
+    pub fn text(&self) -> &'static str {
+        match self {
+            PremiumCard::Jester => "Choose another player. If that player wins the round, you gain a token of affection.",
+            PremiumCard::Assassin => "If another player chooses you with a Guard, they are knocked out of the round instead. Discard the Assassin and draw a new card.",
+            PremiumCard::Guard => "Name a number other than 1 and choose another player. If that player has that number in their hand, they are knocked out of the round.",
+            PremiumCard::Cardinal => "Choose two players. They trade hands. Look at one of their hands.",
+            PremiumCard::Priest => "Look at another player's hand.",
+            PremiumCard::Baroness => "Choose one or two other players. Look at their hands.",
+            PremiumCard::Baron => "You and another player secretly compare hands. The player with the lower value is out of the round.",
+            PremiumCard::Sycophant => "Choose any player. If the next card played has an effect that requires choosing a player, they must choose the player you chose.",
+            PremiumCard::Handmaid => "Until your next turn, ignore all effects from other players' cards.",
+            PremiumCard::Count => "If the Count is in your discard pile at the end of the round, add 1 to the number of the card in your hand.",
+            PremiumCard::Prince => "Choose any player (including yourself) to discard his or her hand and draw a new card.",
+            PremiumCard::Constable => "If you are knocked out of the round while the Constable is in your discard pile, gain a token of affection.",
+            PremiumCard::King => "Trade hands with another player of your choice.",
+            PremiumCard::DowagerQueen => "Choose another player. Secretly compare hands with them. The player with the higher number is knocked out of the round.",
+            PremiumCard::Countess => "If you have this card and the King or Prince in your hand, you must discard this card.",
+            PremiumCard::Princess => "If you discard this card, you are out of the round.",
+            PremiumCard::Bishop => "Name a number other than 1 and choose another player. If they have that number in their hand, gain a token of affection.",
+        }
+    }
+
+    /// The number of copies of this card in a premium deck.
+    pub fn count(&self) -> usize {
+        match self {
+            PremiumCard::Guard => 8,
+            PremiumCard::Cardinal
+            | PremiumCard::Priest
+            | PremiumCard::Baroness
+            | PremiumCard::Baron
+            | PremiumCard::Sycophant
+            | PremiumCard::Handmaid
+            | PremiumCard::Count
+            | PremiumCard::Prince => 2,
+            _ => 1,
+        }
+    }
+
+    /// Returns the PremiumCard that is the same card as `card`, if any.
+    pub fn from_card(card: &dyn Card) -> Option<PremiumCard> {
+        PremiumCard::ALL
+            .iter()
+            .find(|c| c.is_same_card(card))
+            .copied()
+    }
+
+    /// Builds the complete, unshuffled, 32-card deck.
+    pub fn deck() -> Deck {
+        Deck::new(
+            PremiumCard::ALL
+                .iter()
+                .flat_map(|card| (0..card.count()).map(move |_| Box::new(*card) as Box<dyn Card>))
+                .collect(),
+        )
+    }
+}
+
+impl Card for PremiumCard {
+    fn name(&self) -> &str {
+        match self {
+            PremiumCard::Jester => "Jester",
+            PremiumCard::Assassin => "Assassin",
+            PremiumCard::Guard => "Guard",
+            PremiumCard::Cardinal => "Cardinal",
+            PremiumCard::Priest => "Priest",
+            PremiumCard::Baroness => "Baroness",
+            PremiumCard::Baron => "Baron",
+            PremiumCard::Sycophant => "Sycophant",
+            PremiumCard::Handmaid => "Handmaid",
+            PremiumCard::Count => "Count",
+            PremiumCard::Prince => "Prince",
+            PremiumCard::Constable => "Constable",
+            PremiumCard::King => "King",
+            PremiumCard::DowagerQueen => "Dowager Queen",
+            PremiumCard::Countess => "Countess",
+            PremiumCard::Princess => "Princess",
+            PremiumCard::Bishop => "Bishop",
+        }
+    }
+
+    fn value(&self) -> u8 {
+        match self {
+            PremiumCard::Jester | PremiumCard::Assassin => 0,
+            PremiumCard::Guard => 1,
+            PremiumCard::Cardinal | PremiumCard::Priest => 2,
+            PremiumCard::Baroness | PremiumCard::Baron => 3,
+            PremiumCard::Sycophant | PremiumCard::Handmaid => 4,
+            PremiumCard::Count | PremiumCard::Prince => 5,
+            PremiumCard::Constable | PremiumCard::King => 6,
+            PremiumCard::DowagerQueen | PremiumCard::Countess => 7,
+            PremiumCard::Princess => 8,
+            PremiumCard::Bishop => 9,
+        }
+    }
+}
+
+impl Display for PremiumCard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::OriginalCard;
+
+    #[test]
+    fn test_deck() {
+        let mut deck = PremiumCard::deck();
+        assert_eq!(32, deck.cards_remaining());
+
+        let mut dealt = vec![];
+        while let Some(card) = deck.deal_one() {
+            dealt.push(PremiumCard::from_card(card.as_ref()).unwrap());
+        }
+        for card in PremiumCard::ALL.iter() {
+            assert_eq!(card.count(), dealt.iter().filter(|c| *c == card).count());
+        }
+    }
+
+    #[test]
+    fn test_original_cards() {
+        // Every original card is also a premium card.
+        for card in OriginalCard::ALL.iter() {
+            assert!(PremiumCard::from_card(card).is_some());
+        }
+        assert_eq!(
+            Some(OriginalCard::Countess),
+            OriginalCard::from_card(&PremiumCard::Countess)
+        );
+        assert_eq!(None, OriginalCard::from_card(&PremiumCard::DowagerQueen));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::state::card::Card;
+use crate::state::deck::Deck;
+use std::fmt::{Display, Formatter};
+
+/// The ten cards from the 2019 revised edition of Love Letter.
+///
+/// The revised edition adds the Spy and the Chancellor to the original
+/// cards, which moves the King, Countess, and Princess up one value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum RevisedCard {
+    Spy,
+    Guard,
+    Priest,
+    Baron,
+    Handmaid,
+    Prince,
+    Chancellor,
+    King,
+    Countess,
+    Princess,
+}
+
+impl RevisedCard {
+    /// Every card in the revised edition, in order of value.
+    pub const ALL: [RevisedCard; 10] = [
+        RevisedCard::Spy,
+        RevisedCard::Guard,
+        RevisedCard::Priest,
+        RevisedCard::Baron,
+        RevisedCard::Handmaid,
+        RevisedCard::Prince,
+        RevisedCard::Chancellor,
+        RevisedCard::King,
+        RevisedCard::Countess,
+        RevisedCard::Princess,
+    ];
+
+    /// The rules text printed on the card.
+    pub fn text(&self) -> &'static str {
+        match self {
+            RevisedCard::Spy => "At the end of the round, if you are the only player in the round who played or discarded a Spy, gain 1 token of affection.",
+            RevisedCard::Guard => "Choose another player and name a character other than Guard. If the chosen player has that card, they are out of the round.",
+            RevisedCard::Priest => "Choose another player and privately look at their hand.",
+            RevisedCard::Baron => "Choose another player. You and that player secretly compare your hands. Whoever has the lower value is out of the round.",
+            RevisedCard::Handmaid => "Until the start of your next turn, other players cannot choose you for their card effects.",
+            RevisedCard::Prince => "Choose any player (including yourself). That player discards their hand and draws a new one.",
+            RevisedCard::Chancellor => "Draw 2 cards. Keep 1 of the 3 cards now in your hand and place the other 2 on the bottom of the deck in any order.",
+            RevisedCard::King => "Choose another player and trade hands with them.",
+            RevisedCard::Countess => "If the King or Prince is in your hand, you must play the Countess.",
+            RevisedCard::Princess => "If you play or discard the Princess, you are out of the round.",
+        }
+    }
+
+    /// The number of copies of this card in a revised deck.
+    pub fn count(&self) -> usize {
+        match self {
+            RevisedCard::Guard => 6,
+            RevisedCard::Spy
+            | RevisedCard::Priest
+            | RevisedCard::Baron
+            | RevisedCard::Handmaid
+            | RevisedCard::Prince
+            | RevisedCard::Chancellor => 2,
+            RevisedCard::King | RevisedCard::Countess | RevisedCard::Princess => 1,
+        }
+    }
+
+    /// Returns the RevisedCard that is the same card as `card`, if any.
+    pub fn from_card(card: &dyn Card) -> Option<RevisedCard> {
+        RevisedCard::ALL
+            .iter()
+            .find(|c| c.is_same_card(card))
+            .copied()
+    }
+
+    /// Builds the complete, unshuffled, 21-card deck.
+    pub fn deck() -> Deck {
+        Deck::new(
+            RevisedCard::ALL
+                .iter()
+                .flat_map(|card| (0..card.count()).map(move |_| Box::new(*card) as Box<dyn Card>))
+                .collect(),
+        )
+    }
+}
+
+impl Card for RevisedCard {
+    fn name(&self) -> &str {
+        match self {
+            RevisedCard::Spy => "Spy",
+            RevisedCard::Guard => "Guard",
+            RevisedCard::Priest => "Priest",
+            RevisedCard::Baron => "Baron",
+            RevisedCard::Handmaid => "Handmaid",
+            RevisedCard::Prince => "Prince",
+            RevisedCard::Chancellor => "Chancellor",
+            RevisedCard::King => "King",
+            RevisedCard::Countess => "Countess",
+            RevisedCard::Princess => "Princess",
+        }
+    }
+
+    fn value(&self) -> u8 {
+        match self {
+            RevisedCard::Spy => 0,
+            RevisedCard::Guard => 1,
+            RevisedCard::Priest => 2,
+            RevisedCard::Baron => 3,
+            RevisedCard::Handmaid => 4,
+            RevisedCard::Prince => 5,
+            RevisedCard::Chancellor => 6,
+            RevisedCard::King => 7,
+            RevisedCard::Countess => 8,
+            RevisedCard::Princess => 9,
+        }
+    }
+}
+
+impl Display for RevisedCard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::OriginalCard;
+
+    #[test]
+    fn test_deck() {
+        let mut deck = RevisedCard::deck();
+        assert_eq!(21, deck.cards_remaining());
+
+        let mut dealt = vec![];
+        while let Some(card) = deck.deal_one() {
+            dealt.push(RevisedCard::from_card(card.as_ref()).unwrap());
+        }
+        for card in RevisedCard::ALL.iter() {
+            assert_eq!(card.count(), dealt.iter().filter(|c| *c == card).count());
+        }
+    }
+
+    #[test]
+    fn test_original_cards() {
+        // The low cards are unchanged, but the high cards have new values.
+        assert_eq!(
+            Some(RevisedCard::Prince),
+            RevisedCard::from_card(&OriginalCard::Prince)
+        );
+        assert_eq!(None, RevisedCard::from_card(&OriginalCard::King));
+        assert_eq!(None, OriginalCard::from_card(&RevisedCard::Princess));
+    }
+}
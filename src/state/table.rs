@@ -1,34 +1,64 @@
 use crate::state::card::Card;
 use crate::state::deck::Deck;
+use crate::state::identified_card::CardId;
 use crate::state::player::Player;
+use crate::state::player_id::PlayerId;
 use crate::Error;
 use fehler::{throw, throws};
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 
+/// Where a card is on the table.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CardLocation {
+    Deck,
+    /// The card set aside, face down, at the start of the round.
+    OutCard,
+    /// Removed from the deck face up.
+    Burned,
+    Hand(PlayerId),
+    Discards(PlayerId),
+}
+
 #[derive(Debug)]
 pub struct Table {
     players: Vec<Player>,
     deck: Deck,
     out_card: Option<Box<dyn Card>>,
+    /// Cards removed from the deck face up, for everyone to see.
+    burned: Vec<Box<dyn Card>>,
 }
 
 impl Table {
+    /// Creates a table of players named "Player 1", "Player 2", and so on.
     #[throws]
     pub fn new(num_players: usize) -> Table {
-        if num_players != 3 && num_players != 4 {
-            throw!(Error::InvalidNumberOfPlayers(num_players));
+        let names: Vec<String> = (0..num_players)
+            .map(|index| PlayerId(index).to_string())
+            .collect();
+        Table::with_names(&names)?
+    }
+
+    /// Creates a table with one player for each name, seated in order.
+    #[throws]
+    pub fn with_names<S: AsRef<str>>(names: &[S]) -> Table {
+        if names.len() < 2 {
+            throw!(Error::InvalidNumberOfPlayers(names.len()));
         }
-        let mut players = Vec::new();
-        players.push(Player::with_name("1"));
-        players.push(Player::with_name("2"));
-        players.push(Player::with_name("3"));
-        if num_players == 4 {
-            players.push(Player::with_name("4"));
+        for (index, name) in names.iter().enumerate() {
+            if names[..index].iter().any(|n| n.as_ref() == name.as_ref()) {
+                throw!(Error::DuplicatePlayerName(name.as_ref().to_string()));
+            }
         }
+        let players = names
+            .iter()
+            .map(|name| Player::with_name(name.as_ref()))
+            .collect();
         Table {
             players,
             deck: Deck::new(Default::default()),
-	    out_card: None,
+            out_card: None,
+            burned: Vec::new(),
         }
     }
 
@@ -40,41 +70,200 @@ impl Table {
         &mut self.deck
     }
 
+    pub fn set_deck(&mut self, deck: Deck) {
+        self.deck = deck;
+    }
+
     pub fn num_players(&self) -> usize {
         self.players.len()
     }
 
+    /// The ids of every player at the table, in seating order.
+    pub fn player_ids(&self) -> impl Iterator<Item = PlayerId> {
+        (0..self.players.len()).map(PlayerId)
+    }
+
+    /// The name of the player in seat `id`, or the id itself if nobody
+    /// sits there.
+    pub fn name_of(&self, id: PlayerId) -> Cow<'_, str> {
+        match self.players.get(id.index()) {
+            Some(player) => Cow::Borrowed(player.name()),
+            None => Cow::Owned(id.to_string()),
+        }
+    }
+
+    /// Displays the player by name, where `PlayerId` would display their
+    /// seat number.
+    pub fn display(&self, id: PlayerId) -> Named<'_> {
+        Named { table: self, id }
+    }
+
     #[throws]
-    pub fn player(&self, player_num: usize) -> &Player {
-        if player_num >= self.players.len() {
-            throw!(Error::InvalidPlayerNumber(player_num));
+    pub fn player(&self, id: PlayerId) -> &Player {
+        if let Some(player) = self.players.get(id.index()) {
+            player
+        } else {
+            throw!(Error::InvalidPlayerNumber(id));
         }
-        &self.players[player_num]
     }
 
     #[throws]
-    pub fn player_mut(&mut self, player_num: usize) -> &mut Player {
-        if player_num >= self.players.len() {
-            throw!(Error::InvalidPlayerNumber(player_num));
+    pub fn player_mut(&mut self, id: PlayerId) -> &mut Player {
+        if let Some(player) = self.players.get_mut(id.index()) {
+            player
+        } else {
+            throw!(Error::InvalidPlayerNumber(id));
         }
-        &mut self.players[player_num]
     }
 
     pub fn out_card(&self) -> Option<&dyn Card> {
-	self.out_card.as_ref().map(|bc| bc.as_ref())
+        self.out_card.as_ref().map(|bc| bc.as_ref())
+    }
+
+    /// Removes the set-aside card from the table and returns it.
+    pub fn take_out_card(&mut self) -> Option<Box<dyn Card>> {
+        self.out_card.take()
+    }
+
+    /// Exchanges the hands of two players.
+    #[throws]
+    pub fn swap_hands(&mut self, first: PlayerId, second: PlayerId) {
+        self.player(first)?;
+        self.player(second)?;
+        if first != second {
+            let (low, high) = if first < second {
+                (first.index(), second.index())
+            } else {
+                (second.index(), first.index())
+            };
+            let (left, right) = self.players.split_at_mut(high);
+            left[low].swap_hands(&mut right[0]);
+        }
     }
 
     pub fn set_out_card(&mut self, card: Option<Box<dyn Card>>) {
-	self.out_card = card;
+        self.out_card = card;
+    }
+
+    /// The cards that were removed from the deck face up.
+    pub fn burned_cards(&self) -> &[Box<dyn Card>] {
+        &self.burned
+    }
+
+    /// Removes a card from play, face up.
+    pub fn burn_card(&mut self, card: Box<dyn Card>) {
+        self.burned.push(card);
+    }
+
+    /// Every card on the table, and where it is.
+    pub fn cards(&self) -> impl Iterator<Item = (CardLocation, &dyn Card)> {
+        let players = self
+            .player_ids()
+            .zip(&self.players)
+            .flat_map(|(id, player)| {
+                let hand = player
+                    .hand()
+                    .iter()
+                    .map(move |c| (CardLocation::Hand(id), c.as_ref()));
+                let discards = player
+                    .discards()
+                    .iter()
+                    .map(move |c| (CardLocation::Discards(id), c.as_ref()));
+                hand.chain(discards)
+            });
+        let burned = self
+            .burned
+            .iter()
+            .map(|c| (CardLocation::Burned, c.as_ref()));
+        players
+            .chain(self.deck.remaining().map(|c| (CardLocation::Deck, c)))
+            .chain(self.out_card().map(|c| (CardLocation::OutCard, c)))
+            .chain(burned)
+    }
+
+    /// Finds the physical card with the given id, wherever it is.
+    pub fn find_card(&self, id: CardId) -> Option<(CardLocation, &dyn Card)> {
+        self.cards().find(|(_, card)| card.id() == Some(id))
+    }
+
+    /// Checks that the cards on the table are exactly the cards in
+    /// `composition`, matched by name and value, and that no card with an
+    /// id is in more than one place. A card that has been copied is found
+    /// even if the copy replaced a card of the same kind that went missing.
+    #[throws]
+    pub fn audit(&self, composition: &Deck) {
+        let describe = |card: &dyn Card| match card.id() {
+            Some(id) => format!("{} {}", card, id),
+            None => card.to_string(),
+        };
+
+        let mut expected = composition.remaining().collect::<Vec<_>>();
+        let mut seen = Vec::new();
+        for (location, card) in self.cards() {
+            if let Some(id) = card.id() {
+                if seen.contains(&id) {
+                    throw!(Error::InternalErrorInvariantViolated(format!(
+                        "{} in {:?} is in more than one place",
+                        describe(card),
+                        location
+                    )));
+                }
+                seen.push(id);
+            }
+            let index = expected.iter().position(|c| c.is_same_card(card));
+            if let Some(index) = index {
+                expected.swap_remove(index);
+            } else {
+                throw!(Error::InternalErrorInvariantViolated(format!(
+                    "{} in {:?} is not in the deck",
+                    describe(card),
+                    location
+                )));
+            }
+        }
+        if let Some(card) = expected.first() {
+            throw!(Error::InternalErrorInvariantViolated(format!(
+                "{} is missing",
+                describe(*card)
+            )));
+        }
+    }
+}
+
+/// A player displayed by their name at the table.
+#[derive(Clone, Copy, Debug)]
+pub struct Named<'a> {
+    table: &'a Table,
+    id: PlayerId,
+}
+
+impl Named<'_> {
+    pub fn id(&self) -> PlayerId {
+        self.id
+    }
+}
+
+impl Display for Named<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.table.name_of(self.id))
     }
 }
 
 impl Display for Table {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for (num, player) in self.players.iter().enumerate() {
-            writeln!(f, "Player {}", num)?;
+        for player in &self.players {
+            writeln!(f, "{}", player.name())?;
             writeln!(f, "{}", player)?;
         }
+        if !self.burned.is_empty() {
+            let burned_desc = self
+                .burned
+                .iter()
+                .map(|c| c.as_ref().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(f, "Face up: {}", burned_desc)?;
+        }
         write!(f, "Deck: {}", self.deck)
     }
 }
@@ -82,26 +271,172 @@ impl Display for Table {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::state::testcard::TestCard;
     use fehler::throws;
 
     #[test]
     #[throws]
     fn test_three_players() {
         let mut table = Table::new(3)?;
-        assert_eq!("1", table.player(0)?.name());
-        assert_eq!("2", table.player(1)?.name());
-        assert_eq!("3", table.player(2)?.name());
-        assert!(table.player(3).is_err());
+        assert_eq!("Player 1", table.player(PlayerId(0))?.name());
+        assert_eq!("Player 2", table.player(PlayerId(1))?.name());
+        assert_eq!("Player 3", table.player(PlayerId(2))?.name());
+        assert!(table.player(PlayerId(3)).is_err());
     }
 
     #[test]
     #[throws]
     fn test_four_players() {
         let mut table = Table::new(4)?;
-        assert_eq!("1", table.player(0)?.name());
-        assert_eq!("2", table.player(1)?.name());
-        assert_eq!("3", table.player(2)?.name());
-        assert_eq!("4", table.player(3)?.name());
-        assert!(table.player(4).is_err());
+        assert_eq!("Player 1", table.player(PlayerId(0))?.name());
+        assert_eq!("Player 2", table.player(PlayerId(1))?.name());
+        assert_eq!("Player 3", table.player(PlayerId(2))?.name());
+        assert_eq!("Player 4", table.player(PlayerId(3))?.name());
+        assert!(table.player(PlayerId(4)).is_err());
+    }
+
+    #[test]
+    #[throws]
+    fn test_swap_hands() {
+        let mut table = Table::new(3)?;
+        table
+            .player_mut(PlayerId(0))?
+            .add_card_to_hand(TestCard::boxed("Baron", 3));
+        table
+            .player_mut(PlayerId(2))?
+            .add_card_to_hand(TestCard::boxed("King", 6));
+
+        table.swap_hands(PlayerId(2), PlayerId(0))?;
+        assert_eq!("King", table.player(PlayerId(0))?.card_in_hand()?.name());
+        assert_eq!("Baron", table.player(PlayerId(2))?.card_in_hand()?.name());
+        assert!(table.swap_hands(PlayerId(0), PlayerId(3)).is_err());
+    }
+
+    #[test]
+    fn test_take_out_card() {
+        let mut table = Table::new(3).unwrap();
+        table.set_out_card(Some(TestCard::boxed("Princess", 8)));
+        assert_eq!("Princess", table.take_out_card().unwrap().name());
+        assert!(table.out_card().is_none());
+    }
+
+    #[test]
+    #[throws]
+    fn test_two_players() {
+        let table = Table::new(2)?;
+        assert_eq!(2, table.num_players());
+        assert!(Table::new(1).is_err());
+    }
+
+    #[test]
+    #[throws]
+    fn test_with_names() {
+        let table = Table::with_names(&["Alice", "Bob", "Carol"])?;
+        assert_eq!(3, table.num_players());
+        assert_eq!("Bob", table.player(PlayerId(1))?.name());
+        assert_eq!("Carol", table.name_of(PlayerId(2)));
+        assert_eq!("Player 4", table.name_of(PlayerId(3)));
+        assert_eq!(
+            "Bob is out",
+            format!("{} is out", table.display(PlayerId(1)))
+        );
+        assert!(table.to_string().starts_with("Alice\n"));
+
+        assert_eq!(
+            Some(Error::InvalidNumberOfPlayers(1)),
+            Table::with_names(&["Alice"]).err()
+        );
+        assert_eq!(
+            Some(Error::DuplicatePlayerName("Bob".to_string())),
+            Table::with_names(&["Bob", "Alice", "Bob"]).err()
+        );
+    }
+
+    #[test]
+    #[throws]
+    fn test_burn_card() {
+        let mut table = Table::new(2)?;
+        assert!(table.burned_cards().is_empty());
+        assert!(!table.to_string().contains("Face up"));
+
+        table.burn_card(TestCard::boxed("Baron", 3));
+        table.burn_card(TestCard::boxed("Guard", 1));
+        assert_eq!(2, table.burned_cards().len());
+        assert!(table
+            .to_string()
+            .contains("Face up: TestCard: 3:Baron, TestCard: 1:Guard"));
+    }
+
+    #[test]
+    #[throws]
+    fn test_find_card() {
+        let mut table = Table::new(2)?;
+        table.set_deck(Deck::new(vec![
+            TestCard::boxed("Guard", 1),
+            TestCard::boxed("Guard", 1),
+            TestCard::boxed("Guard", 1),
+            TestCard::boxed("Guard", 1),
+            TestCard::boxed("Guard", 1),
+        ]));
+        for player in 0..2 {
+            let card = table.deck_mut().deal_one().unwrap();
+            table.player_mut(PlayerId(player))?.add_card_to_hand(card);
+        }
+        let card = table.deck_mut().deal_one();
+        table.set_out_card(card);
+        let card = table.deck_mut().deal_one().unwrap();
+        table.burn_card(card);
+        table.player_mut(PlayerId(1))?.discard(0)?;
+
+        let location = |id| table.find_card(CardId(id)).map(|(location, _)| location);
+        assert_eq!(Some(CardLocation::Hand(PlayerId(0))), location(0));
+        assert_eq!(Some(CardLocation::Discards(PlayerId(1))), location(1));
+        assert_eq!(Some(CardLocation::OutCard), location(2));
+        assert_eq!(Some(CardLocation::Burned), location(3));
+        assert_eq!(Some(CardLocation::Deck), location(4));
+        assert_eq!(None, location(5));
+
+        table.swap_hands(PlayerId(0), PlayerId(1))?;
+        assert_eq!(
+            Some(CardLocation::Hand(PlayerId(1))),
+            table.find_card(CardId(0)).map(|(location, _)| location)
+        );
+    }
+
+    #[test]
+    #[throws]
+    fn test_audit() {
+        let cards = || {
+            Deck::new(vec![
+                TestCard::boxed("Guard", 1),
+                TestCard::boxed("Guard", 1),
+                TestCard::boxed("Priest", 2),
+            ])
+        };
+        let mut table = Table::new(2)?;
+        table.set_deck(cards());
+        for player in 0..2 {
+            let card = table.deck_mut().deal_one().unwrap();
+            table.player_mut(PlayerId(player))?.add_card_to_hand(card);
+        }
+        table.audit(&cards())?;
+
+        // A Guard is copied from one hand to the other.
+        let copy = table.player(PlayerId(0))?.hand()[0].clone();
+        table.player_mut(PlayerId(1))?.add_card_to_hand(copy);
+        assert!(matches!(
+            table.audit(&cards()),
+            Err(Error::InternalErrorInvariantViolated(_))
+        ));
+        table.player_mut(PlayerId(1))?.take_from_hand(1)?;
+
+        // The Priest goes missing.
+        table.deck_mut().deal_one();
+        assert_eq!(
+            Some(Error::InternalErrorInvariantViolated(
+                "TestCard: 2:Priest #2 is missing".to_string()
+            )),
+            table.audit(&cards()).err()
+        );
     }
 }